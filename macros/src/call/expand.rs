@@ -23,6 +23,23 @@ pub fn expand_call(def: CallDef) -> proc_macro2::TokenStream {
 		.map(|method| method.args.iter().map(|(_, type_)| type_.clone()).collect::<Vec<_>>())
 		.collect::<Vec<_>>();
 
+	// This is the first argument passed to each of the functions in `fn_name`. Signed calls get
+	// the `caller` extracted from the origin, and fail with `BadOrigin` otherwise. The other calls
+	// get the origin as is.
+	let caller_arg = methods
+		.iter()
+		.map(|method| {
+			if method.signed {
+				quote! { crate::support::ensure_signed(origin)? }
+			} else {
+				quote! { origin }
+			}
+		})
+		.collect::<Vec<_>>();
+
+	// This is a nested vector of the doc comment lines of each of the functions in `fn_name`.
+	let docs = methods.iter().map(|method| method.docs.clone()).collect::<Vec<_>>();
+
 	// This quote block creates an `enum Call` which contains all the calls exposed by our pallet,
 	// and the `Dispatch` trait logic to route a `caller` to access those functions.
 	let dispatch_impl = quote! {
@@ -39,16 +56,17 @@ pub fn expand_call(def: CallDef) -> proc_macro2::TokenStream {
 		// Dispatch logic at the pallet level, mapping each of the items in the `Call` enum to the
 		// appropriate function call with all arguments, including the `caller`.
 		impl<T: Config> crate::support::Dispatch for #pallet_struct<T> {
-			type Caller = T::AccountId;
+			type Caller = crate::support::Origin<T::AccountId>;
 			type Call = Call<T>;
 
-			fn dispatch(&mut self, caller: Self::Caller, call: Self::Call) -> crate::support::DispatchResult {
+			fn dispatch(&mut self, origin: Self::Caller, call: Self::Call) -> crate::support::DispatchResult {
 				match call {
 					#(
 						Call::#fn_name { #( #args_name ),* } => {
 							self.#fn_name(
-								// Note that we assume the first argument of every call is the `caller`.
-								caller,
+								// Note that we assume the first argument of every call is the `caller`
+								// or the `origin`.
+								#caller_arg,
 								#( #args_name ),*
 							)?;
 						},
@@ -59,6 +77,36 @@ pub fn expand_call(def: CallDef) -> proc_macro2::TokenStream {
 		}
 	};
 
+	// This quote block exposes the name of each call, used by the runtime to add context to the
+	// errors of failed extrinsics, and the metadata of all the calls.
+	let name_impl = quote! {
+		impl<T: Config> Call<T> {
+			// The name of the pallet function this call maps to.
+			pub fn name(&self) -> &'static str {
+				match self {
+					#( Call::#fn_name { .. } => stringify!(#fn_name), )*
+				}
+			}
+
+			// The name and the doc comment of every call exposed by this pallet, so tools can
+			// describe the calls without reading the source.
+			pub fn metadata() -> Vec<crate::support::CallMetadata> {
+				vec![
+					#(
+						crate::support::CallMetadata {
+							name: stringify!(#fn_name),
+							docs: &[ #( #docs ),* ],
+						},
+					)*
+				]
+			}
+		}
+	};
+
 	// Return the generated code.
-	dispatch_impl.into()
+	quote! {
+		#dispatch_impl
+		#name_impl
+	}
+	.into()
 }
@@ -25,6 +25,12 @@ pub struct CallVariantDef {
 	pub name: syn::Ident,
 	/// Information on args of the function: `(name, type)`.
 	pub args: Vec<(syn::Ident, Box<syn::Type>)>,
+	/// `true` when the second parameter is `caller: T::AccountId`, meaning the call can only be
+	/// dispatched from a signed origin. `false` when the call takes the raw `origin` and checks it
+	/// itself (for example, root-only calls).
+	pub signed: bool,
+	/// The lines of the doc comment of the function, exposed as metadata of the call.
+	pub docs: Vec<String>,
 }
 
 impl CallDef {
@@ -59,18 +65,21 @@ impl CallDef {
 					},
 				}
 
-				// The second argument should be the `caller: T::AccountId` argument.
-				match method.sig.inputs.iter().skip(1).next() {
+				// The second argument should be the `caller: T::AccountId` argument, or the raw
+				// `origin` for calls which check the origin themselves.
+				let signed = match method.sig.inputs.iter().skip(1).next() {
+					Some(syn::FnArg::Typed(arg)) if is_origin_arg(arg) => false,
 					Some(syn::FnArg::Typed(arg)) => {
 						// Here we specifically check that this argument is as we expect for
 						// `caller: T::AccountId`.
 						check_caller_arg(arg)?;
+						true
 					},
 					_ => {
 						let msg = "Invalid call, second argument should be `caller: T::AccountId`";
 						return Err(syn::Error::new(method.sig.span(), msg))
 					},
-				}
+				};
 
 				let fn_name = method.sig.ident.clone();
 
@@ -95,8 +104,10 @@ impl CallDef {
 					args.push((arg_ident, arg.ty.clone()));
 				}
 
+				let docs = doc_lines(&method.attrs);
+
 				// Store all the function name and the arg data for the function.
-				methods.push(CallVariantDef { name: fn_name, args });
+				methods.push(CallVariantDef { name: fn_name, args, signed, docs });
 			}
 		}
 
@@ -105,6 +116,31 @@ impl CallDef {
 	}
 }
 
+/// Collect the lines of the doc comment (`///` or `#[doc = "..."]`) in `attrs`.
+pub fn doc_lines(attrs: &[syn::Attribute]) -> Vec<String> {
+	attrs
+		.iter()
+		.filter(|attr| attr.path().is_ident("doc"))
+		.filter_map(|attr| match &attr.meta {
+			syn::Meta::NameValue(syn::MetaNameValue {
+				value: syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(doc), .. }),
+				..
+			}) => Some(doc.value().trim().to_string()),
+			_ => None,
+		})
+		.collect()
+}
+
+/// Check if the arg is named `origin` (or `_origin`). Such calls receive the
+/// `support::Origin` of the call as is, instead of the signed `caller`. The type itself is
+/// checked by the compiler when the generated `Dispatch` passes the origin to the function.
+fn is_origin_arg(arg: &syn::PatType) -> bool {
+	match &*arg.pat {
+		syn::Pat::Ident(ident) => ident.ident == "origin" || ident.ident == "_origin",
+		_ => false,
+	}
+}
+
 /// Check caller arg is exactly: `caller: T::AccountId`.
 ///
 /// This is kept strict to keep the code simple.
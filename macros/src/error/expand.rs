@@ -0,0 +1,33 @@
+use super::parse::ErrorDef;
+use quote::quote;
+
+/// See the `fn error` docs at the `lib.rs` of this crate for a high level definition.
+pub fn expand_error(def: ErrorDef) -> proc_macro2::TokenStream {
+	let ErrorDef { mut item_mod, errors } = def;
+
+	// The names of all the constants, and the doc comment lines of each one of them.
+	let error_name = errors.iter().map(|(name, _)| name).collect::<Vec<_>>();
+	let docs = errors.iter().map(|(_, docs)| docs.clone()).collect::<Vec<_>>();
+
+	// This function exposes the metadata of all the errors, like `Event::metadata()` does for the
+	// events. Since the errors are plain `&'static str`, the message itself identifies the error.
+	let metadata: syn::Item = syn::parse_quote! {
+		// The message and the doc comment of every error returned by this pallet, so tools can
+		// explain a failed extrinsic without reading the source.
+		pub fn metadata() -> Vec<crate::support::ErrorMetadata> {
+			vec![
+				#(
+					crate::support::ErrorMetadata {
+						message: #error_name,
+						docs: &[ #( #docs ),* ],
+					},
+				)*
+			]
+		}
+	};
+	if let Some((_, items)) = &mut item_mod.content {
+		items.push(metadata);
+	}
+
+	quote! { #item_mod }
+}
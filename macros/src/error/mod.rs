@@ -0,0 +1,19 @@
+pub mod expand;
+pub mod parse;
+
+/// See the `fn error` docs at the `lib.rs` of this crate for a high level definition.
+pub fn error(
+	_attr: proc_macro::TokenStream,
+	item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+	// Unlike `call` and `event`, the generated function goes inside the module, so the module is
+	// rebuilt by `expand` instead of being kept as is.
+	let item_mod = syn::parse_macro_input!(item as syn::Item);
+
+	// First we parse the error constants of the module...
+	match parse::ErrorDef::try_from(item_mod) {
+		// ..then we generate the module again, with our new code inside.
+		Ok(def) => expand::expand_error(def).into(),
+		Err(e) => e.to_compile_error().into(),
+	}
+}
@@ -0,0 +1,45 @@
+use syn::spanned::Spanned;
+
+/// This object will collect all the information we need to keep while parsing the errors of a
+/// pallet.
+#[derive(Debug)]
+pub struct ErrorDef {
+	/// The module with the error constants, kept as is.
+	pub item_mod: syn::ItemMod,
+	/// The error constants of the module, as `(name, doc comment lines)`.
+	pub errors: Vec<(syn::Ident, Vec<String>)>,
+}
+
+impl ErrorDef {
+	pub fn try_from(item: syn::Item) -> syn::Result<Self> {
+		// We can only describe errors declared inside a `mod { ... }`.
+		let item_mod = if let syn::Item::Mod(item) = item {
+			item
+		} else {
+			return Err(syn::Error::new(item.span(), "Invalid pallet::error, expected item mod"))
+		};
+		let items = match &item_mod.content {
+			Some((_, items)) => items,
+			None => {
+				return Err(syn::Error::new(
+					item_mod.span(),
+					"Invalid pallet::error, expected an inline module",
+				))
+			},
+		};
+
+		// Every `const` of the module is an error message. Other items are ignored.
+		let errors = items
+			.iter()
+			.filter_map(|item| match item {
+				syn::Item::Const(item_const) => Some((
+					item_const.ident.clone(),
+					crate::call::parse::doc_lines(&item_const.attrs),
+				)),
+				_ => None,
+			})
+			.collect();
+
+		Ok(Self { item_mod, errors })
+	}
+}
@@ -0,0 +1,31 @@
+use super::parse::EventDef;
+use quote::quote;
+
+/// See the `fn event` docs at the `lib.rs` of this crate for a high level definition.
+pub fn expand_event(def: EventDef) -> proc_macro2::TokenStream {
+	let EventDef { enum_name, generics, variants } = def;
+	let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+	// The names of all the variants, and the doc comment lines of each one of them.
+	let variant_name = variants.iter().map(|(name, _)| name).collect::<Vec<_>>();
+	let docs = variants.iter().map(|(_, docs)| docs.clone()).collect::<Vec<_>>();
+
+	// This quote block exposes the metadata of all the events, like `Call::metadata()` does for
+	// the calls.
+	quote! {
+		impl #impl_generics #enum_name #ty_generics #where_clause {
+			// The name and the doc comment of every event emitted by this pallet, so tools can
+			// describe the events without reading the source.
+			pub fn metadata() -> Vec<crate::support::EventMetadata> {
+				vec![
+					#(
+						crate::support::EventMetadata {
+							name: stringify!(#variant_name),
+							docs: &[ #( #docs ),* ],
+						},
+					)*
+				]
+			}
+		}
+	}
+}
@@ -0,0 +1,32 @@
+use syn::spanned::Spanned;
+
+/// This object will collect all the information we need to keep while parsing the events of a
+/// pallet.
+#[derive(Debug)]
+pub struct EventDef {
+	/// The name of the event enum. We mostly assume it is `Event`.
+	pub enum_name: syn::Ident,
+	/// The generics of the event enum, usually `<T: Config>`.
+	pub generics: syn::Generics,
+	/// The variants of the event enum, as `(name, doc comment lines)`.
+	pub variants: Vec<(syn::Ident, Vec<String>)>,
+}
+
+impl EventDef {
+	pub fn try_from(item: syn::Item) -> syn::Result<Self> {
+		// We can only describe events declared as an `enum`.
+		let item_enum = if let syn::Item::Enum(item) = item {
+			item
+		} else {
+			return Err(syn::Error::new(item.span(), "Invalid pallet::event, expected item enum"))
+		};
+
+		let variants = item_enum
+			.variants
+			.iter()
+			.map(|variant| (variant.ident.clone(), crate::call::parse::doc_lines(&variant.attrs)))
+			.collect();
+
+		Ok(Self { enum_name: item_enum.ident, generics: item_enum.generics, variants })
+	}
+}
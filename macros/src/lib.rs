@@ -1,6 +1,21 @@
 mod call;
+mod error;
+mod event;
 mod runtime;
 
+/// Expand the callable functions of a pallet.
+///
+/// This generates:
+/// - `enum Call` - with one variant for each function in the `impl` block, containing all the
+///   arguments of the function except `self` and the caller.
+/// - implements the trait `support::Dispatch` for the pallet, routing each variant of `Call` to
+///   its function.
+/// - `Call::name()` and `Call::metadata()` - the name of a call, and the name and doc comment of
+///   every call, as `support::CallMetadata`.
+///
+/// The second parameter of every function must be either `caller: T::AccountId`, for calls which
+/// must be signed, or `origin`, for calls which check the `support::Origin` themselves (for
+/// example, calls only allowed from `Root`).
 #[proc_macro_attribute]
 pub fn call(
 	attr: proc_macro::TokenStream,
@@ -9,6 +24,33 @@ pub fn call(
 	call::call(attr, item)
 }
 
+/// Expand the event enum of a pallet.
+///
+/// This generates `Event::metadata()` - the name and doc comment of every variant of the enum, as
+/// `support::EventMetadata`. The generics of the enum are kept, so it works for `Event<T: Config>`
+/// as well as for events without generics.
+#[proc_macro_attribute]
+pub fn event(
+	attr: proc_macro::TokenStream,
+	item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+	event::event(attr, item)
+}
+
+/// Expand the error module of a pallet.
+///
+/// Errors are plain `&'static str`, so a pallet documents them as constants inside an inline
+/// `mod errors { ... }`, and returns the constants instead of writing the messages again. This
+/// generates `errors::metadata()` inside the module - the message and doc comment of every
+/// `const` of the module, as `support::ErrorMetadata`.
+#[proc_macro_attribute]
+pub fn error(
+	attr: proc_macro::TokenStream,
+	item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+	error::error(attr, item)
+}
+
 /// Expand the `Runtime` definition.
 ///
 /// This generates function implementations on `Runtime`:
@@ -16,15 +58,35 @@ pub fn call(
 ///   included in the runtime.
 /// - `fn execute_block()` - which handles basic logic for executing a block of extrinsics. It does
 ///   basic actions like incrementing the block number and checking the block to be executed has a
-///   valid block number.
+///   valid block number and at most `system::Config::MAX_EXTRINSICS_PER_BLOCK` extrinsics. The
+///   `support::Hooks` of every pallet are called before (`on_initialize`) and after
+///   (`on_finalize`) the extrinsics, so every pallet must implement that trait. In debug builds,
+///   the `try_state` of every pallet is checked at the end of the block. Before being
+///   dispatched, each extrinsic is charged through the `support::ChargeTransaction` trait, which
+///   the `Runtime` must implement; extrinsics which cannot be charged are rejected. After each
+///   extrinsic, the events emitted by the pallets are moved to the system pallet, followed by a
+///   `system::Event` with the outcome of the extrinsic. After a successful dispatch, and after
+///   `on_finalize`, the changes pallets asked of each other are applied through the
+///   `support::Settle` trait, which the `Runtime` must implement. If a dispatch fails, panics or
+///   cannot be settled, the pallets are restored to their state before the dispatch and the
+///   extrinsic is marked as failed. For that, every pallet must implement
+///   `support::Transactional`.
 ///
 /// This also generates code needed for dispatching calls to the pallets:
 /// - Note: For simplicity, we assume that the system pallet is not callable.
 /// - `enum RuntimeCall` - an "outer"-enum representing the accumulation of all possible calls to
-///   all pallets. The system pallet is not included.
+///   all pallets. The system pallet is not included. `RuntimeCall::metadata()` describes every
+///   pallet with its calls, events and errors (see `support::PalletMetadata`), so the `Event` of
+///   every callable pallet must use `#[macros::event]`, and every callable pallet must have a
+///   `mod errors` using `#[macros::error]`. `pallet_name()`/`call_name()`
+///   identify a single call.
 /// - implements the trait `support::Dispatch` to dispatch calls to the appropriate pallet. Basic
 ///   logic like incrementing the nonce of the user is included in the generated code. The system
 ///   pallet is not included.
+/// - `enum RuntimeEvent` - an "outer"-enum representing the accumulation of all possible events of
+///   all pallets, including the system pallet. Every pallet is expected to have an `Event<T>` type
+///   implementing `support::EventTopics`, and a `take_events()` function draining the events it
+///   emitted. `RuntimeEvent` implements `support::EventTopics` by asking the pallet event.
 #[proc_macro_attribute]
 pub fn runtime(
 	attr: proc_macro::TokenStream,
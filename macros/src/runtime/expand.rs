@@ -25,22 +25,150 @@ pub fn expand_runtime(def: RuntimeDef) -> proc_macro2::TokenStream {
 			}
 
 			// Execute a block of extrinsics. Increments the block number.
+			//
+			// Each extrinsic is first charged through the `ChargeTransaction` trait, which the
+			// runtime must implement, and only then dispatched.
+			//
+			// Before and after the extrinsics, the `Hooks` of every pallet are called.
+			//
+			// The outcome of every extrinsic is recorded as a system event, keyed by the index of
+			// the extrinsic in the block. An extrinsic whose dispatch fails or panics is rolled
+			// back and recorded as failed, and the rest of the block is still executed.
 			fn execute_block(&mut self, block: types::Block) -> crate::support::DispatchResult {
+				// Over-full blocks are rejected before anything is executed.
+				let max_extrinsics = <#runtime_struct as system::Config>::MAX_EXTRINSICS_PER_BLOCK;
+				if block.extrinsic.len() > max_extrinsics as usize {
+					return Err(&"block has too many extrinsics")
+				}
 				self.system.inc_block_number();
 				if block.header.block_number != self.system.block_number() {
 					return Err(&"block number does not match what is expected")
 				}
-				for (i, support::Extrinsic { caller, call }) in block.extrinsic.into_iter().enumerate() {
-					self.system.inc_nonce(&caller);
-					let _res = self.dispatch(caller, call).map_err(|e| {
+				// Every pallet gets a chance to run its own logic before the extrinsics.
+				#(
+					crate::support::Hooks::on_initialize(
+						&mut self.#pallet_names,
+						block.header.block_number,
+					);
+				)*
+				self.deposit_pallet_events(system::Phase::Initialization);
+				for (i, support::Extrinsic { caller, call, tip }) in block.extrinsic.into_iter().enumerate() {
+					let phase = system::Phase::ApplyExtrinsic(i as u32);
+					// The extrinsic is charged (e.g. fees and its optional tip) before being
+					// dispatched. If the caller cannot pay, the extrinsic is rejected and has no
+					// effect at all.
+					if let Err(error) = crate::support::ChargeTransaction::charge_transaction(
+						self, &caller, &call, tip,
+					) {
+						#( self.#pallet_names.take_events(); )*
 						eprintln!(
-							"Extrinsic Error\n\tBlock Number: {}\n\tExtrinsic Number: {}\n\tError: {}",
-							block.header.block_number, i, e
-						)
-					});
+							"Extrinsic Rejected\n\tBlock Number: {}\n\tExtrinsic Number: {}\n\tError: {}",
+							block.header.block_number, i, error
+						);
+						let event = system::Event::ExtrinsicFailed { error: error.into() };
+						self.system.deposit_event(phase, RuntimeEvent::system(event));
+						continue;
+					}
+					// The charge is kept even if the dispatch fails, and so are its events.
+					// Errors of the dispatch are reported with the pallet and call that failed.
+					let (pallet_name, call_name) = (call.pallet_name(), call.call_name());
+					self.deposit_pallet_events(phase);
+					self.system.inc_nonce(&caller);
+					// Every pallet starts a transaction before the dispatch. If the dispatch fails,
+					// including when the changes it asked of other pallets cannot be settled, the
+					// changes made by the extrinsic are rolled back. A panic in a pallet must not take
+					// the whole block down, so it is caught and handled the same way.
+					#( crate::support::Transactional::begin(&mut self.#pallet_names); )*
+					let origin = crate::support::Origin::Signed(caller);
+					// Extrinsics are always signed by the `caller`.
+					let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+						self.dispatch(origin, call)?;
+						crate::support::Settle::settle(self)
+					}))
+					.unwrap_or_else(|_| Err("Extrinsic panicked"));
+					match result {
+						Ok(()) => {
+							#( crate::support::Transactional::commit(&mut self.#pallet_names); )*
+							self.deposit_pallet_events(phase);
+							let event = system::Event::ExtrinsicSuccess;
+							self.system.deposit_event(phase, RuntimeEvent::system(event));
+						},
+						Err(error) => {
+							// Events emitted by a failed extrinsic are discarded.
+							#( crate::support::Transactional::rollback(&mut self.#pallet_names); )*
+							#( self.#pallet_names.take_events(); )*
+							let error = crate::support::DispatchError {
+								pallet: Some(pallet_name),
+								call: Some(call_name),
+								error,
+							};
+							eprintln!(
+								"Extrinsic Error\n\tBlock Number: {}\n\tExtrinsic Number: {}\n\tError: {}",
+								block.header.block_number, i, error
+							);
+							let event = system::Event::ExtrinsicFailed { error };
+							self.system.deposit_event(phase, RuntimeEvent::system(event));
+						},
+					}
+				}
+				// And after them, for example to apply per-block changes to its state.
+				#(
+					crate::support::Hooks::on_finalize(
+						&mut self.#pallet_names,
+						block.header.block_number,
+					);
+				)*
+				crate::support::Settle::settle(self)?;
+				self.deposit_pallet_events(system::Phase::Finalization);
+				// In debug builds (and tests) the state of every pallet is checked after each
+				// block, so bugs which break its invariants are caught right away.
+				if cfg!(debug_assertions) {
+					#( crate::support::Hooks::<types::BlockNumber>::try_state(&self.#pallet_names)?; )*
 				}
 				Ok(())
 			}
+
+			// Move the events emitted by the pallets to the system pallet, where they can be
+			// queried per block, recording them under `phase`.
+			fn deposit_pallet_events(&mut self, phase: system::Phase) {
+				#(
+					for event in self.#pallet_names.take_events() {
+						self.system.deposit_event(phase, RuntimeEvent::#pallet_names(event));
+					}
+				)*
+			}
+		}
+	};
+
+	// This quote block implements the `RuntimeEvent` enum.
+	let event_impl = quote! {
+		// These are all the events which can be emitted by the runtime. Like `RuntimeCall`, it is
+		// just an accumulation of the events of each pallet, but here `system` is included.
+		//
+		// Pallets keep their events in a buffer, which is drained by `execute_block` after each
+		// extrinsic and stored in the system pallet.
+		#[allow(non_camel_case_types)]
+		#[derive(Debug)]
+		pub enum RuntimeEvent {
+			system(system::Event),
+			#( #pallet_names(#pallet_names::Event<#runtime_struct>) ),*
+		}
+
+		// The accounts mentioned by an event are those of the pallet event it wraps. The system
+		// pallet uses them to index the events of each block by account.
+		impl crate::support::EventTopics<<#runtime_struct as system::Config>::AccountId>
+			for RuntimeEvent
+		{
+			fn accounts(&self) -> Vec<&<#runtime_struct as system::Config>::AccountId> {
+				match self {
+					RuntimeEvent::system(event) => crate::support::EventTopics::accounts(event),
+					#(
+						RuntimeEvent::#pallet_names(event) => {
+							crate::support::EventTopics::accounts(event)
+						}
+					)*
+				}
+			}
 		}
 	};
 
@@ -55,17 +183,48 @@ pub fn expand_runtime(def: RuntimeDef) -> proc_macro2::TokenStream {
 			#( #pallet_names(#pallet_names::Call<#runtime_struct>) ),*
 		}
 
+		impl RuntimeCall {
+			// The name of the pallet this call is routed to.
+			pub fn pallet_name(&self) -> &'static str {
+				match self {
+					#( RuntimeCall::#pallet_names(_) => stringify!(#pallet_names), )*
+				}
+			}
+
+			// The name of the call inside its pallet.
+			pub fn call_name(&self) -> &'static str {
+				match self {
+					#( RuntimeCall::#pallet_names(call) => call.name(), )*
+				}
+			}
+
+			// The metadata of every callable pallet in the runtime, with the docs of its calls, events
+			// and errors.
+			pub fn metadata() -> Vec<crate::support::PalletMetadata> {
+				vec![
+					#(
+						crate::support::PalletMetadata {
+							name: stringify!(#pallet_names),
+							calls: #pallet_names::Call::<#runtime_struct>::metadata(),
+							events: #pallet_names::Event::<#runtime_struct>::metadata(),
+							errors: #pallet_names::errors::metadata(),
+						},
+					)*
+				]
+			}
+		}
+
 		impl crate::support::Dispatch for #runtime_struct {
-			type Caller = <Runtime as system::Config>::AccountId;
+			type Caller = crate::support::Origin<<Runtime as system::Config>::AccountId>;
 			type Call = RuntimeCall;
-			// Dispatch a call on behalf of a caller. Increments the caller's nonce.
+			// Dispatch a call on behalf of an origin.
 			//
 			// Dispatch allows us to identify which underlying pallet call we want to execute.
-			// Note that we extract the `caller` from the extrinsic, and use that information
-			// to determine who we are executing the call on behalf of.
+			// Note that extrinsics are dispatched with the signed origin of their `caller`, while
+			// privileged code (like genesis or tests) can dispatch calls with the `Root` origin.
 			fn dispatch(
 				&mut self,
-				caller: Self::Caller,
+				origin: Self::Caller,
 				runtime_call: Self::Call,
 			) -> crate::support::DispatchResult {
 				// This match statement will allow us to correctly route `RuntimeCall`s
@@ -73,7 +232,7 @@ pub fn expand_runtime(def: RuntimeDef) -> proc_macro2::TokenStream {
 				match runtime_call {
 					#(
 						RuntimeCall::#pallet_names(call) => {
-							self.#pallet_names.dispatch(caller, call)?;
+							self.#pallet_names.dispatch(origin, call)?;
 						}
 					),*
 				}
@@ -84,6 +243,7 @@ pub fn expand_runtime(def: RuntimeDef) -> proc_macro2::TokenStream {
 
 	// We combine and return all the generated code.
 	quote! {
+		#event_impl
 		#dispatch_impl
 		#runtime_impl
 	}
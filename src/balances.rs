@@ -1,5 +1,62 @@
-use num::traits::{CheckedAdd, CheckedSub, Zero};
-use std::collections::BTreeMap;
+use crate::support::{
+    ensure_root, Currency, DispatchResult, EventTopics, Get, Hooks, JournaledMap, Origin,
+    Transactional,
+};
+use num::traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, Zero};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Identificador de um lock de saldo, por exemplo `*b"staking "` ou `*b"vesting "`.
+/// Cada pallet usa o seu próprio identificador para não sobrescrever o lock de outro
+pub type LockIdentifier = [u8; 8];
+
+/// Motivo de um hold de saldo, por exemplo `*b"poe/clm "`.
+/// Cada pallet usa os seus próprios motivos, e os holds de motivos diferentes se somam
+pub type HoldReason = [u8; 8];
+
+/// Taxa aplicada ao saldo livre das contas a cada bloco, em partes por milhão.
+/// Por exemplo, `Interest(1_000)` rende 0,1% por bloco
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Accrual {
+    /// Juros: novas moedas são criadas e somadas ao saldo
+    Interest(u32),
+
+    /// Demurrage: parte do saldo é destruída, o que pode remover contas que ficarem com poeira.
+    /// A parte travada por locks ou em hold não é afetada
+    Demurrage(u32),
+}
+
+/// Chamado quando fundos entram em uma conta: transferências recebidas, `mint`
+/// e reservas repatriadas. O runtime usa `()` quando não precisa reagir a isso.
+/// O pallet guarda uma instância do gancho, então ele pode manter o seu próprio estado
+pub trait OnDeposit<AccountId, Amount> {
+    fn on_deposit(&mut self, who: &AccountId, amount: Amount);
+}
+
+impl<AccountId, Amount> OnDeposit<AccountId, Amount> for () {
+    fn on_deposit(&mut self, _who: &AccountId, _amount: Amount) {}
+}
+
+/// Chamado quando fundos saem de uma conta: transferências enviadas, `burn`/`withdraw`
+/// e cortes (`slash_reserved`, `repatriate_reserved`).
+/// Mover fundos entre o saldo livre e o reservado não conta como saída
+pub trait OnWithdraw<AccountId, Amount> {
+    fn on_withdraw(&mut self, who: &AccountId, amount: Amount);
+}
+
+impl<AccountId, Amount> OnWithdraw<AccountId, Amount> for () {
+    fn on_withdraw(&mut self, _who: &AccountId, _amount: Amount) {}
+}
+
+/// Chamado quando uma conta é removida por ficar abaixo do depósito existencial,
+/// com a poeira que foi destruída
+pub trait HandleDust<AccountId, Amount> {
+    fn on_dust(&mut self, who: &AccountId, dust: Amount);
+}
+
+impl<AccountId, Amount> HandleDust<AccountId, Amount> for () {
+    fn on_dust(&mut self, _who: &AccountId, _dust: Amount) {}
+}
+
 /**
  * Criamos uma trait para encapsular todos os types que são necessários no Pallet.
  * Isso é muito útil para situações em que precisamos passar muitos types como parâmetros
@@ -8,7 +65,168 @@ use std::collections::BTreeMap;
 pub trait Config {
     // definição de tipos
     type AccountId: Ord + Clone;
-    type Amount: Zero + CheckedSub + CheckedAdd + Copy;
+    type Amount: Zero + CheckedSub + CheckedAdd + CheckedMul + CheckedDiv + From<u32> + Copy + Ord;
+
+    /// Saldo mínimo para uma conta existir (depósito existencial).
+    /// Quando o saldo total de uma conta fica abaixo desse valor, a conta é removida
+    /// e o que sobrou (poeira) é destruído
+    const EXISTENTIAL_DEPOSIT: Self::Amount;
+
+    /// Menor valor aceito em uma transferência, independente do depósito existencial.
+    /// Transferências menores falham com `BelowMinimum`, exceto pelo `force_transfer`
+    type MinimumTransfer: Get<Self::Amount>;
+
+    /// Tamanho máximo, em bytes, do memo de uma `transfer_with_memo`
+    const MAX_MEMO_LENGTH: u32;
+
+    /// Juros ou demurrage aplicados ao saldo livre de todas as contas ao final de cada bloco.
+    /// Com `None`, os saldos não mudam sozinhos
+    const ACCRUAL: Option<Accrual>;
+
+    /// Ganchos chamados pelo pallet quando fundos entram ou saem de uma conta,
+    /// e quando uma conta é removida com poeira. Permitem que outros módulos
+    /// reajam a essas mudanças sem alterar o balances.
+    /// Cada gancho é criado com `Default` junto com o pallet e acessado com `hooks_mut`
+    type OnDeposit: OnDeposit<Self::AccountId, Self::Amount> + Default + Clone;
+    type OnWithdraw: OnWithdraw<Self::AccountId, Self::Amount> + Default + Clone;
+    type OnDust: HandleDust<Self::AccountId, Self::Amount> + Default + Clone;
+}
+
+/// Eventos emitidos pelo pallet de balances.
+/// Ficam guardados no pallet até o runtime movê-los para o system ao final de cada extrinsic
+#[macros::event]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event<T: Config> {
+    /// `amount` foi transferido de `from` para `to`
+    Transfer {
+        from: T::AccountId,
+        to: T::AccountId,
+        amount: T::Amount,
+    },
+
+    /// O saldo livre de `who` foi definido como `free` pela origem `Root`
+    BalanceSet { who: T::AccountId, free: T::Amount },
+
+    /// `amount` novas moedas foram criadas na conta `who`
+    Minted {
+        who: T::AccountId,
+        amount: T::Amount,
+    },
+
+    /// `amount` moedas da conta `who` foram destruídas
+    Burned {
+        who: T::AccountId,
+        amount: T::Amount,
+    },
+
+    /// `amount` do saldo livre de `who` foi reservado
+    Reserved {
+        who: T::AccountId,
+        amount: T::Amount,
+    },
+
+    /// `amount` do saldo reservado de `who` voltou para o saldo livre
+    Unreserved {
+        who: T::AccountId,
+        amount: T::Amount,
+    },
+
+    /// `amount` do saldo de `who` foi cortado e destruído
+    Slashed {
+        who: T::AccountId,
+        amount: T::Amount,
+    },
+
+    /// `amount` do saldo reservado de `from` foi movido para o saldo livre de `to`
+    ReserveRepatriated {
+        from: T::AccountId,
+        to: T::AccountId,
+        amount: T::Amount,
+    },
+
+    /// `owner` autorizou `spender` a gastar até `amount` em seu nome
+    Approval {
+        owner: T::AccountId,
+        spender: T::AccountId,
+        amount: T::Amount,
+    },
+
+    /// A conta `who` ficou abaixo do depósito existencial e foi removida.
+    /// `dust` é o saldo que sobrou e foi destruído
+    Reaped { who: T::AccountId, dust: T::Amount },
+
+    /// A conta `who` foi congelada e não pode mais enviar nem receber transferências
+    Frozen { who: T::AccountId },
+
+    /// A conta `who` foi descongelada
+    Thawed { who: T::AccountId },
+
+    /// `amount` do saldo livre de `who` foi colocado em hold pelo motivo `reason`
+    Held {
+        reason: HoldReason,
+        who: T::AccountId,
+        amount: T::Amount,
+    },
+
+    /// `amount` do hold de `who` com o motivo `reason` foi liberado
+    Released {
+        reason: HoldReason,
+        who: T::AccountId,
+        amount: T::Amount,
+    },
+
+    /// A transferência de `from` para `to` foi feita com o `memo` informado
+    Memo {
+        from: T::AccountId,
+        to: T::AccountId,
+        memo: Vec<u8>,
+    },
+}
+
+/// Erros retornados pelo pallet de balances
+#[macros::error]
+pub mod errors {
+    /// O saldo livre da conta não cobre o valor da operação
+    pub const INSUFFICIENT_BALANCE: &str = "Insufficient balance";
+
+    /// O `caller` não foi autorizado a gastar esse valor em nome da conta, com `approve`
+    pub const INSUFFICIENT_ALLOWANCE: &str = "Insufficient allowance";
+
+    /// A transferência é menor que o valor mínimo de transferência
+    pub const BELOW_MINIMUM: &str = "BelowMinimum";
+
+    /// O memo passa do tamanho máximo configurado
+    pub const MEMO_TOO_LONG: &str = "Memo too long";
+
+    /// A conta está congelada e não pode enviar nem receber transferências
+    pub const ACCOUNT_FROZEN: &str = "Account is frozen";
+
+    /// O saldo que sobra não cobre os locks da conta
+    pub const BALANCE_LOCKED: &str = "Balance is locked";
+
+    /// O saldo que sobra não cobre os holds da conta
+    pub const BALANCE_ON_HOLD: &str = "Balance is on hold";
+
+    /// A conta que recebe os fundos ficaria abaixo do depósito existencial
+    pub const BELOW_EXISTENTIAL_DEPOSIT: &str = "Balance below existential deposit";
+
+    /// Com `keep_alive`, a conta de origem ficaria abaixo do depósito existencial
+    pub const WOULD_KILL_ACCOUNT: &str = "Transfer would kill account";
+
+    /// O novo saldo livre da conta não cabe no tipo `Amount`
+    pub const BALANCE_OVERFLOW: &str = "Overflow when adding to balance";
+
+    /// O novo saldo reservado da conta não cabe no tipo `Amount`
+    pub const RESERVED_OVERFLOW: &str = "Overflow when adding to reserved balance";
+
+    /// O novo total em hold da conta não cabe no tipo `Amount`
+    pub const HOLD_OVERFLOW: &str = "Overflow when adding to balance on hold";
+
+    /// O novo total em circulação não cabe no tipo `Amount`
+    pub const TOTAL_ISSUANCE_OVERFLOW: &str = "Total issuance overflow";
+
+    /// O valor destruído é maior que o total em circulação
+    pub const TOTAL_ISSUANCE_UNDERFLOW: &str = "Total issuance underflow";
 }
 
 // Pallet é como se fosse um módulo.
@@ -23,7 +241,43 @@ pub struct Pallet<T: Config> {
     // pode ser o endereço da carteira ou nome e o saldo
     // evidente que num mundo real, os dados são armazenados em banco de dados
     // no nosso caso aqui, estamos armazenando em memória
-    balance: BTreeMap<T::AccountId, T::Amount>,
+    balance: JournaledMap<T::AccountId, T::Amount>,
+
+    // saldo reservado (bloqueado) de cada conta.
+    // esse saldo ainda pertence à conta, mas não pode ser transferido
+    // até que seja liberado com `unreserve`
+    reserved: JournaledMap<T::AccountId, T::Amount>,
+
+    // locks de saldo de cada conta, identificados por `LockIdentifier`.
+    // os locks se sobrepõem: o valor travado é o maior deles, e não a soma
+    locks: JournaledMap<T::AccountId, BTreeMap<LockIdentifier, T::Amount>>,
+
+    // holds de saldo de cada conta, separados por motivo.
+    // ao contrário dos locks, os holds se somam: cada um ocupa uma parte do saldo livre
+    holds: JournaledMap<T::AccountId, BTreeMap<HoldReason, T::Amount>>,
+
+    // quanto cada `spender` pode gastar em nome de cada `owner`
+    // <(owner, spender), amount>
+    allowances: JournaledMap<(T::AccountId, T::AccountId), T::Amount>,
+
+    // contas congeladas pela origem `Root`, que não podem enviar nem receber transferências
+    frozen: JournaledMap<T::AccountId, ()>,
+
+    // soma de todos os saldos existentes, ou seja, o total de moedas em circulação
+    total_issuance: T::Amount,
+
+    // eventos emitidos durante a extrinsic atual, ainda não recolhidos pelo runtime
+    events: Vec<Event<T>>,
+
+    // instâncias dos ganchos configurados, com o estado que cada um precisar guardar
+    on_deposit: T::OnDeposit,
+    on_withdraw: T::OnWithdraw,
+    on_dust: T::OnDust,
+
+    // o total em circulação e os ganchos no `begin` da transação aberta, para o `rollback`.
+    // os mapas guardam o que precisam por conta própria
+    #[allow(clippy::type_complexity)]
+    checkpoint: Option<(T::Amount, T::OnDeposit, T::OnWithdraw, T::OnDust)>,
 }
 
 /// implementamos o struct Pallet, mas apenas com as funções que queremos expor para uso.
@@ -48,8 +302,8 @@ impl<T: Config> Pallet<T> {
     ///
     /// ```
     /// let mut balances = Pallet::new();
-    /// balances.set_balance(&"Alice".to_string(), 100);
-    /// balances.set_balance(&"Bob".to_string(), 50);
+    /// balances.set_balance(&"Alice".to_string(), 100).unwrap();
+    /// balances.set_balance(&"Bob".to_string(), 50).unwrap();
     /// let result = balances.transfer("Alice".to_string(), "Bob".to_string(), 30);
     /// assert!(result.is_ok());
     /// ```
@@ -59,31 +313,300 @@ impl<T: Config> Pallet<T> {
         to: T::AccountId,
         amount: T::Amount,
     ) -> Result<(), &'static str> {
-        // recupero o saldo de quem está querendo transferir
-        let caller_balance = self.get_balance(&caller);
+        // transferências muito pequenas só criariam contas e eventos sem valor
+        self.ensure_minimum_transfer(amount)?;
+        self.do_transfer(caller, to, amount)
+    }
 
-        // recupero o saldo para quem vai o 'amount'
-        let to_balance = self.get_balance(&to);
+    /// Transfere fundos do `caller` para várias contas em uma única extrinsic.
+    ///
+    /// O total é verificado contra o saldo do `caller`, e o crédito de cada destinatário
+    /// (overflow e depósito existencial) é verificado antes de qualquer mudança, então as
+    /// transferências são atômicas: ou todas acontecem, ou nenhuma acontece.
+    /// Assim como em `do_transfer`, uma transferência para o próprio `caller` não muda nada
+    pub fn batch_transfer(
+        &mut self,
+        caller: T::AccountId,
+        transfers: Vec<(T::AccountId, T::Amount)>,
+    ) -> Result<(), &'static str> {
+        self.ensure_not_frozen(&caller)?;
+        for (to, amount) in &transfers {
+            self.ensure_not_frozen(to)?;
+            self.ensure_minimum_transfer(*amount)?;
+        }
 
-        // novo saldo de quem quer fazer a transferência
-        // subtraindo o valor do saldo existente.
-        // importante é que devemos verificar se o caller_balance
-        // tem saldo, caso sim, o resultado é ok, caso contrário
-        // lançamos um erro estático: 'Insufficient balance'
-        let new_caller_balance = caller_balance
+        // somamos o valor de todas as transferências antes de mexer em qualquer saldo
+        let total = transfers
+            .iter()
+            .try_fold(T::Amount::zero(), |total, (_, amount)| {
+                total.checked_add(amount)
+            })
+            .ok_or(errors::BALANCE_OVERFLOW)?;
+
+        let new_caller_balance = self
+            .get_balance(&caller)
+            .checked_sub(&total)
+            .ok_or(errors::INSUFFICIENT_BALANCE)?;
+        self.ensure_can_withdraw(&caller, new_caller_balance)?;
+
+        // o que o `caller` manda para si mesmo continua com ele
+        let mut credits: BTreeMap<&T::AccountId, T::Amount> = BTreeMap::new();
+        let mut debit = T::Amount::zero();
+        for (to, amount) in transfers.iter().filter(|(to, _)| *to != caller) {
+            let credit = credits.entry(to).or_insert_with(T::Amount::zero);
+            *credit = credit.checked_add(amount).ok_or(errors::BALANCE_OVERFLOW)?;
+            debit = debit + *amount;
+        }
+
+        // um destinatário pode aparecer mais de uma vez: verificamos o saldo final de cada um
+        let mut new_balances = Vec::with_capacity(credits.len());
+        for (to, credit) in credits {
+            let new_balance = self
+                .get_balance(to)
+                .checked_add(&credit)
+                .ok_or(errors::BALANCE_OVERFLOW)?;
+            self.ensure_existential_deposit(to, new_balance)?;
+            new_balances.push((to, new_balance));
+        }
+
+        // daqui para frente nada pode falhar
+        let caller_balance = self.get_balance(&caller) - debit;
+        self.write_balance(&caller, caller_balance);
+        for (to, new_balance) in new_balances {
+            self.write_balance(to, new_balance);
+        }
+
+        self.on_withdraw.on_withdraw(&caller, debit);
+        for (to, amount) in transfers.iter().filter(|(to, _)| *to != caller) {
+            self.on_deposit.on_deposit(to, *amount);
+            self.deposit_event(Event::Transfer {
+                from: caller.clone(),
+                to: to.clone(),
+                amount: *amount,
+            });
+        }
+
+        // se o `caller` ficou só com poeira, a conta é removida
+        self.reap_if_dust(&caller);
+
+        Ok(())
+    }
+
+    /// Igual ao `transfer`, mas falha em vez de remover a conta do `caller`
+    /// quando o saldo que sobra ficaria abaixo do depósito existencial
+    pub fn transfer_keep_alive(
+        &mut self,
+        caller: T::AccountId,
+        to: T::AccountId,
+        amount: T::Amount,
+    ) -> Result<(), &'static str> {
+        // se o saldo não cobre o valor, o `transfer` devolve o erro de saldo insuficiente
+        if let Some(new_free) = self.get_balance(&caller).checked_sub(&amount) {
+            let new_total = new_free
+                .checked_add(&self.reserved_balance(&caller))
+                .ok_or(errors::BALANCE_OVERFLOW)?;
+            if caller != to && new_total < T::EXISTENTIAL_DEPOSIT {
+                return Err(errors::WOULD_KILL_ACCOUNT);
+            }
+        }
+
+        self.transfer(caller, to, amount)
+    }
+
+    /// Igual ao `transfer`, mas registra um `memo` (por exemplo, a referência de um pagamento)
+    /// em um evento. O memo não pode passar de `MAX_MEMO_LENGTH` bytes
+    pub fn transfer_with_memo(
+        &mut self,
+        caller: T::AccountId,
+        to: T::AccountId,
+        amount: T::Amount,
+        memo: Vec<u8>,
+    ) -> Result<(), &'static str> {
+        if memo.len() > T::MAX_MEMO_LENGTH as usize {
+            return Err(errors::MEMO_TOO_LONG);
+        }
+
+        self.transfer(caller.clone(), to.clone(), amount)?;
+        self.deposit_event(Event::Memo {
+            from: caller,
+            to,
+            memo,
+        });
+        Ok(())
+    }
+
+    /// Transfere todo o saldo transferível do `caller` para `to`.
+    ///
+    /// Fundos reservados, em hold ou travados por locks nunca são movidos.
+    /// Com `keep_alive`, o depósito existencial fica na conta para que ela não seja removida.
+    /// Se a conta tiver fundos reservados, em hold ou travados, o depósito existencial também
+    /// fica mesmo sem `keep_alive`, já que remover a conta destruiria esses fundos
+    pub fn transfer_all(
+        &mut self,
+        caller: T::AccountId,
+        to: T::AccountId,
+        keep_alive: bool,
+    ) -> Result<(), &'static str> {
+        let encumbered = !self.locked_balance(&caller).is_zero()
+            || !self.total_balance_on_hold(&caller).is_zero()
+            || !self.reserved_balance(&caller).is_zero();
+        let amount = self.reducible_balance(&caller, keep_alive || encumbered);
+
+        // nada para transferir
+        if amount.is_zero() {
+            return Ok(());
+        }
+
+        self.transfer(caller, to, amount)
+    }
+
+    /// Autoriza `spender` a gastar até `amount` do saldo do `caller` com `transfer_from`.
+    /// Uma nova aprovação substitui a anterior, e aprovar zero remove a autorização
+    pub fn approve(
+        &mut self,
+        caller: T::AccountId,
+        spender: T::AccountId,
+        amount: T::Amount,
+    ) -> Result<(), &'static str> {
+        if amount.is_zero() {
+            self.allowances.remove(&(caller.clone(), spender.clone()));
+        } else {
+            self.allowances
+                .insert((caller.clone(), spender.clone()), amount);
+        }
+        self.deposit_event(Event::Approval {
+            owner: caller,
+            spender,
+            amount,
+        });
+        Ok(())
+    }
+
+    /// Transfere `amount` de `from` para `to` em nome de `from`.
+    /// O `caller` precisa ter sido autorizado por `from` com `approve`,
+    /// e a autorização é consumida pelo valor transferido
+    pub fn transfer_from(
+        &mut self,
+        caller: T::AccountId,
+        from: T::AccountId,
+        to: T::AccountId,
+        amount: T::Amount,
+    ) -> Result<(), &'static str> {
+        let key = (from, caller);
+        let new_allowance = self
+            .allowances
+            .get(&key)
+            .copied()
+            .unwrap_or(T::Amount::zero())
             .checked_sub(&amount)
-            .ok_or("Insufficient balance")?;
+            .ok_or(errors::INSUFFICIENT_ALLOWANCE)?;
 
-        // novo saldo de quem vai receber o 'amount'
-        let new_to_balance = to_balance
+        // a transferência faz todas as verificações de saldo e locks
+        self.transfer(key.0.clone(), to, amount)?;
+
+        // só consumimos a autorização se a transferência deu certo
+        if new_allowance.is_zero() {
+            self.allowances.remove(&key);
+        } else {
+            self.allowances.insert(key, new_allowance);
+        }
+
+        Ok(())
+    }
+
+    /// Cria `amount` novas moedas na conta `account`, aumentando o `total_issuance`.
+    /// Só pode ser chamada com a origem `Root`.
+    pub fn mint(
+        &mut self,
+        origin: Origin<T::AccountId>,
+        account: T::AccountId,
+        amount: T::Amount,
+    ) -> Result<(), &'static str> {
+        ensure_root(origin)?;
+
+        let new_balance = self
+            .get_balance(&account)
             .checked_add(&amount)
-            .ok_or("Overflow when adding to balance")?;
+            .ok_or(errors::BALANCE_OVERFLOW)?;
+        let new_total_issuance = self
+            .total_issuance
+            .checked_add(&amount)
+            .ok_or(errors::TOTAL_ISSUANCE_OVERFLOW)?;
+        self.ensure_existential_deposit(&account, new_balance)?;
 
-        // agora atualizamos os saldos
-        self.set_balance(&caller, new_caller_balance);
-        self.set_balance(&to, new_to_balance);
+        self.write_balance(&account, new_balance);
+        self.total_issuance = new_total_issuance;
+        self.on_deposit.on_deposit(&account, amount);
+        self.deposit_event(Event::Minted {
+            who: account,
+            amount,
+        });
 
-        // tudo certo
+        Ok(())
+    }
+
+    /// Destrói `amount` moedas da conta `account`, diminuindo o `total_issuance`.
+    /// Só pode ser chamada com a origem `Root`.
+    pub fn burn(
+        &mut self,
+        origin: Origin<T::AccountId>,
+        account: T::AccountId,
+        amount: T::Amount,
+    ) -> Result<(), &'static str> {
+        ensure_root(origin)?;
+        self.withdraw(&account, amount)
+    }
+
+    /// Transfere fundos de `from` para `to` sem a assinatura de `from`.
+    /// Só pode ser chamada com a origem `Root`, e faz as mesmas verificações de `transfer`,
+    /// exceto a do valor mínimo: é assim que casos especiais movem valores abaixo dele
+    pub fn force_transfer(
+        &mut self,
+        origin: Origin<T::AccountId>,
+        from: T::AccountId,
+        to: T::AccountId,
+        amount: T::Amount,
+    ) -> Result<(), &'static str> {
+        ensure_root(origin)?;
+        self.do_transfer(from, to, amount)
+    }
+
+    /// Define o saldo livre de `who`, ajustando o `total_issuance`.
+    /// Só pode ser chamada com a origem `Root`
+    pub fn force_set_balance(
+        &mut self,
+        origin: Origin<T::AccountId>,
+        who: T::AccountId,
+        amount: T::Amount,
+    ) -> Result<(), &'static str> {
+        ensure_root(origin)?;
+        self.set_balance(&who, amount)?;
+        self.deposit_event(Event::BalanceSet { who, free: amount });
+        Ok(())
+    }
+
+    /// Congela a conta `who`: enquanto congelada, ela não pode enviar nem receber transferências.
+    /// Só pode ser chamada com a origem `Root`
+    pub fn freeze(
+        &mut self,
+        origin: Origin<T::AccountId>,
+        who: T::AccountId,
+    ) -> Result<(), &'static str> {
+        ensure_root(origin)?;
+        self.frozen.insert(who.clone(), ());
+        self.deposit_event(Event::Frozen { who });
+        Ok(())
+    }
+
+    /// Descongela a conta `who`. Só pode ser chamada com a origem `Root`
+    pub fn thaw(
+        &mut self,
+        origin: Origin<T::AccountId>,
+        who: T::AccountId,
+    ) -> Result<(), &'static str> {
+        ensure_root(origin)?;
+        self.frozen.remove(&who);
+        self.deposit_event(Event::Thawed { who });
         Ok(())
     }
 }
@@ -97,85 +620,1958 @@ impl<T: Config> Pallet<T> {
         // Aqui podemos criar um novo objeto do tipo Pallet
         // quando quero um novo objeto, basta chamar Pallet::new()
         Pallet {
-            balance: BTreeMap::new(),
+            balance: JournaledMap::new(),
+            reserved: JournaledMap::new(),
+            locks: JournaledMap::new(),
+            holds: JournaledMap::new(),
+            allowances: JournaledMap::new(),
+            frozen: JournaledMap::new(),
+            total_issuance: T::Amount::zero(),
+            events: Vec::new(),
+            on_deposit: T::OnDeposit::default(),
+            on_withdraw: T::OnWithdraw::default(),
+            on_dust: T::OnDust::default(),
+            checkpoint: None,
         }
     }
 
     // inserimos no map o amount na conta definida.
     // o '&mut self' indica que algo vai mudar entro desse Pallet,
-    // ou seja, &mut pemite que read/write
-    pub fn set_balance(&mut self, account: &T::AccountId, amount: T::Amount) {
-        // Aqui podemos adicionar um novo saldo
-        self.balance.insert(account.clone(), amount);
-    }
-
-    pub fn get_balance(&self, account: &T::AccountId) -> T::Amount {
-        // Aqui podemos pegar o saldo de uma carteira se ela existir,
-        // caso contrário retorna zero
-        //! note que tem o '*' no início, o que significa que é um
-        //! ponteiro para o própria instância de balance (&self)
-        *self
-            .balance
-            .get(&account.clone())
-            .unwrap_or(&T::Amount::zero())
-    }
-}
+    // ou seja, &mut pemite que read/write.
+    // falha, sem mudar nada, se o novo total em circulação não couber no tipo `Amount`
+    pub fn set_balance(
+        &mut self,
+        account: &T::AccountId,
+        amount: T::Amount,
+    ) -> Result<(), &'static str> {
+        // o saldo antigo sai de circulação e o novo entra,
+        // assim o total_issuance continua igual à soma dos saldos
+        let old_amount = self.get_balance(account);
+        self.total_issuance = self
+            .total_issuance
+            .checked_sub(&old_amount)
+            .and_then(|total| total.checked_add(&amount))
+            .ok_or(errors::TOTAL_ISSUANCE_OVERFLOW)?;
 
-#[cfg(test)]
-mod test {
-    struct TestConfig;
+        self.write_balance(account, amount);
+        self.cap_holds(account);
 
-    impl super::Config for TestConfig {
-        type AccountId = String;
-        type Amount = u32;
+        // um saldo abaixo do depósito existencial remove a conta
+        self.reap_if_dust(account);
+        Ok(())
     }
 
-    #[test]
-    fn init_balances() {
-        let mut balances: super::Pallet<TestConfig> = super::Pallet::new();
-        balances.set_balance(&"Lucio".to_string(), 100);
-        balances.set_balance(&"Miriam".to_string(), 300);
+    pub fn total_issuance(&self) -> T::Amount {
+        self.total_issuance
     }
 
-    #[test]
-    fn success_tranfer() {
-        // instanciamos o Pallet de balances
-        let mut balances: super::Pallet<TestConfig> = super::Pallet::new();
+    /// Retira `amount` do saldo livre da conta e o destrói, diminuindo o `total_issuance`.
+    /// Respeita os locks da conta, e uma conta que fica abaixo do depósito existencial é removida.
+    /// É o que a chamada `burn` faz, e também é usada para cobrar taxas
+    pub fn withdraw(
+        &mut self,
+        account: &T::AccountId,
+        amount: T::Amount,
+    ) -> Result<(), &'static str> {
+        let new_balance = self
+            .get_balance(account)
+            .checked_sub(&amount)
+            .ok_or(errors::INSUFFICIENT_BALANCE)?;
+        self.ensure_can_withdraw(account, new_balance)?;
+        let new_total_issuance = self
+            .total_issuance
+            .checked_sub(&amount)
+            .ok_or(errors::TOTAL_ISSUANCE_UNDERFLOW)?;
 
-        // defino os usuários (account)
-        let miriam = "Miriam".to_string();
-        let lucio = "Miriam".to_string();
+        self.write_balance(account, new_balance);
+        self.total_issuance = new_total_issuance;
+        self.on_withdraw.on_withdraw(account, amount);
+        self.deposit_event(Event::Burned {
+            who: account.clone(),
+            amount,
+        });
+        self.reap_if_dust(account);
 
-        // definimos os valores iniciais de cada conta
-        balances.set_balance(&miriam, 200);
-        balances.set_balance(&lucio, 100);
+        Ok(())
+    }
 
-        // '_' para ignorar o retorno do 'transfer'
-        // miriam transfere 50 para o lucio
-        let _ = balances.transfer(miriam.clone(), lucio.clone(), 50);
+    /// Cria `amount` no saldo livre da conta, aumentando o `total_issuance`.
+    /// Ao contrário de uma transferência, não verifica o congelamento nem o depósito existencial:
+    /// serve para creditar um valor já retirado de outra conta, como as taxas.
+    /// Se a conta continuar abaixo do depósito existencial, o valor é destruído como poeira
+    pub fn deposit(
+        &mut self,
+        account: &T::AccountId,
+        amount: T::Amount,
+    ) -> Result<(), &'static str> {
+        if amount.is_zero() {
+            return Ok(());
+        }
 
-        // miriam tem agora 150?
-        assert_eq!(balances.get_balance(&miriam), 150);
+        let new_balance = self
+            .get_balance(account)
+            .checked_add(&amount)
+            .ok_or(errors::BALANCE_OVERFLOW)?;
+        let new_total_issuance = self
+            .total_issuance
+            .checked_add(&amount)
+            .ok_or(errors::TOTAL_ISSUANCE_OVERFLOW)?;
 
-        // lucio agora tem 150?
-        assert_eq!(balances.get_balance(&lucio), 150);
+        self.write_balance(account, new_balance);
+        self.total_issuance = new_total_issuance;
+        self.on_deposit.on_deposit(account, amount);
+        self.deposit_event(Event::Minted {
+            who: account.clone(),
+            amount,
+        });
+        self.reap_if_dust(account);
+
+        Ok(())
     }
 
-    #[test]
-    fn insufficient_balance() {
-        // instanciamos o Pallet de balances
-        let mut balances: super::Pallet<TestConfig> = super::Pallet::new();
+    /// Quanto `spender` ainda pode gastar em nome de `owner`
+    pub fn allowance(&self, owner: &T::AccountId, spender: &T::AccountId) -> T::Amount {
+        *self
+            .allowances
+            .get(&(owner.clone(), spender.clone()))
+            .unwrap_or(&T::Amount::zero())
+    }
 
-        // defino os usuários (account)
-        let caller: String = "Lucio".to_string();
-        let to: String = "Miriam".to_string();
+    /// Saldo reservado da conta, que não pode ser transferido
+    pub fn reserved_balance(&self, account: &T::AccountId) -> T::Amount {
+        *self.reserved.get(account).unwrap_or(&T::Amount::zero())
+    }
 
-        // defino o saldo da miriam para 1500
-        balances.set_balance(&caller, 1500);
+    /// Saldo total da conta: livre + reservado
+    pub fn total_balance(&self, account: &T::AccountId) -> T::Amount {
+        self.get_balance(account)
+            .checked_add(&self.reserved_balance(account))
+            .expect("Total balance overflow")
+    }
+
+    /// Move `amount` do saldo livre para o saldo reservado da conta.
+    /// Falha se o saldo livre não for suficiente
+    pub fn reserve(
+        &mut self,
+        account: &T::AccountId,
+        amount: T::Amount,
+    ) -> Result<(), &'static str> {
+        let new_free = self
+            .get_balance(account)
+            .checked_sub(&amount)
+            .ok_or(errors::INSUFFICIENT_BALANCE)?;
+        self.ensure_can_withdraw(account, new_free)?;
+        let new_reserved = self
+            .reserved_balance(account)
+            .checked_add(&amount)
+            .ok_or(errors::RESERVED_OVERFLOW)?;
+
+        self.write_balance(account, new_free);
+        self.write_reserved(account, new_reserved);
+        self.deposit_event(Event::Reserved {
+            who: account.clone(),
+            amount,
+        });
+
+        Ok(())
+    }
+
+    /// Move até `amount` do saldo reservado de volta para o saldo livre.
+    /// Retorna a parte de `amount` que não pôde ser liberada (zero se tudo foi liberado)
+    pub fn unreserve(&mut self, account: &T::AccountId, amount: T::Amount) -> T::Amount {
+        let reserved = self.reserved_balance(account);
+        let actual = amount.min(reserved);
+
+        // o saldo total da conta não muda, então a soma não pode estourar
+        let new_free = self
+            .get_balance(account)
+            .checked_add(&actual)
+            .expect("Free balance overflow");
+
+        self.write_reserved(account, reserved.checked_sub(&actual).unwrap());
+        self.write_balance(account, new_free);
+        if !actual.is_zero() {
+            self.deposit_event(Event::Unreserved {
+                who: account.clone(),
+                amount: actual,
+            });
+        }
+
+        amount.checked_sub(&actual).unwrap()
+    }
+
+    /// Corta até `amount` da conta como punição, primeiro do saldo livre e depois do reservado,
+    /// destruindo o valor cortado. Locks e holds não impedem o corte, e os holds que passarem
+    /// do novo saldo livre são cortados junto com ele.
+    /// Retorna `(cortado, restante)`, onde `restante` é o que não pôde ser cortado
+    pub fn slash(&mut self, account: &T::AccountId, amount: T::Amount) -> (T::Amount, T::Amount) {
+        let free = self.get_balance(account);
+        let reserved = self.reserved_balance(account);
+        let from_free = amount.min(free);
+        let from_reserved = amount.checked_sub(&from_free).unwrap().min(reserved);
+        let slashed = from_free.checked_add(&from_reserved).unwrap();
+
+        if slashed.is_zero() {
+            return (slashed, amount);
+        }
+
+        if !from_free.is_zero() {
+            self.write_balance(account, free.checked_sub(&from_free).unwrap());
+            self.cap_holds(account);
+        }
+        self.write_reserved(account, reserved.checked_sub(&from_reserved).unwrap());
+        self.total_issuance = self
+            .total_issuance
+            .checked_sub(&slashed)
+            .expect("Total issuance underflow");
+        self.on_withdraw.on_withdraw(account, slashed);
+        self.deposit_event(Event::Slashed {
+            who: account.clone(),
+            amount: slashed,
+        });
+        self.reap_if_dust(account);
+
+        (slashed, amount.checked_sub(&slashed).unwrap())
+    }
+
+    /// Destrói até `amount` do saldo reservado da conta, diminuindo o `total_issuance`.
+    /// Retorna a parte de `amount` que não pôde ser cortada
+    pub fn slash_reserved(&mut self, account: &T::AccountId, amount: T::Amount) -> T::Amount {
+        let reserved = self.reserved_balance(account);
+        let actual = amount.min(reserved);
+
+        self.write_reserved(account, reserved.checked_sub(&actual).unwrap());
+        self.total_issuance = self
+            .total_issuance
+            .checked_sub(&actual)
+            .expect("Total issuance underflow");
+        if !actual.is_zero() {
+            self.on_withdraw.on_withdraw(account, actual);
+            self.deposit_event(Event::Slashed {
+                who: account.clone(),
+                amount: actual,
+            });
+        }
+        self.reap_if_dust(account);
+
+        amount.checked_sub(&actual).unwrap()
+    }
+
+    /// Move até `amount` do saldo reservado de `slashed` para o saldo livre de `beneficiary`.
+    /// Retorna a parte de `amount` que não pôde ser movida
+    pub fn repatriate_reserved(
+        &mut self,
+        slashed: &T::AccountId,
+        beneficiary: &T::AccountId,
+        amount: T::Amount,
+    ) -> Result<T::Amount, &'static str> {
+        // devolver para a própria conta é o mesmo que liberar a reserva
+        if slashed == beneficiary {
+            return Ok(self.unreserve(slashed, amount));
+        }
+
+        let reserved = self.reserved_balance(slashed);
+        let actual = amount.min(reserved);
+
+        let new_beneficiary_balance = self
+            .get_balance(beneficiary)
+            .checked_add(&actual)
+            .ok_or(errors::BALANCE_OVERFLOW)?;
+        self.ensure_existential_deposit(beneficiary, new_beneficiary_balance)?;
+
+        self.write_reserved(slashed, reserved.checked_sub(&actual).unwrap());
+        self.write_balance(beneficiary, new_beneficiary_balance);
+        if !actual.is_zero() {
+            self.on_withdraw.on_withdraw(slashed, actual);
+            self.on_deposit.on_deposit(beneficiary, actual);
+        }
+        self.deposit_event(Event::ReserveRepatriated {
+            from: slashed.clone(),
+            to: beneficiary.clone(),
+            amount: actual,
+        });
+        self.reap_if_dust(slashed);
+
+        Ok(amount.checked_sub(&actual).unwrap())
+    }
+
+    // escreve o saldo da conta sem mexer no total_issuance.
+    // quem chama é responsável por manter o total em sincronia
+    fn write_balance(&mut self, account: &T::AccountId, amount: T::Amount) {
+        // se a conta já existe, apenas atualizamos o saldo,
+        // evitando clonar o `AccountId` a cada transferência
+        match self.balance.get_mut(account) {
+            Some(balance) => *balance = amount,
+            None => {
+                self.balance.insert(account.clone(), amount);
+            }
+        }
+    }
+
+    /// Cria ou atualiza o lock `id` da conta para `amount`.
+    /// Enquanto o lock existir, o saldo livre da conta não pode ficar abaixo de `amount`
+    pub fn set_lock(&mut self, id: LockIdentifier, account: &T::AccountId, amount: T::Amount) {
+        if amount.is_zero() {
+            self.remove_lock(id, account);
+            return;
+        }
+
+        match self.locks.get_mut(account) {
+            Some(locks) => {
+                locks.insert(id, amount);
+            }
+            None => {
+                self.locks
+                    .insert(account.clone(), BTreeMap::from([(id, amount)]));
+            }
+        }
+    }
+
+    /// Remove o lock `id` da conta, se existir
+    pub fn remove_lock(&mut self, id: LockIdentifier, account: &T::AccountId) {
+        if let Some(locks) = self.locks.get_mut(account) {
+            locks.remove(&id);
+            if locks.is_empty() {
+                self.locks.remove(account);
+            }
+        }
+    }
+
+    /// Coloca `amount` do saldo livre da conta em hold pelo motivo `reason`.
+    /// O valor continua no saldo livre, mas não pode ser transferido até ser liberado.
+    /// Falha se o saldo livre não cobrir todos os holds da conta
+    pub fn hold(
+        &mut self,
+        reason: HoldReason,
+        account: &T::AccountId,
+        amount: T::Amount,
+    ) -> Result<(), &'static str> {
+        // um hold de zero não muda nada
+        if amount.is_zero() {
+            return Ok(());
+        }
+
+        let new_total_held = self
+            .total_balance_on_hold(account)
+            .checked_add(&amount)
+            .ok_or(errors::HOLD_OVERFLOW)?;
+        if new_total_held > self.get_balance(account) {
+            return Err(errors::INSUFFICIENT_BALANCE);
+        }
+
+        let held = self.holds.get_or_default(account.clone());
+        let new_held = held
+            .get(&reason)
+            .copied()
+            .unwrap_or(T::Amount::zero())
+            .checked_add(&amount)
+            .unwrap();
+        held.insert(reason, new_held);
+        self.deposit_event(Event::Held {
+            reason,
+            who: account.clone(),
+            amount,
+        });
+
+        Ok(())
+    }
+
+    /// Libera até `amount` do hold `reason` da conta.
+    /// Retorna a parte de `amount` que não pôde ser liberada
+    pub fn release(
+        &mut self,
+        reason: HoldReason,
+        account: &T::AccountId,
+        amount: T::Amount,
+    ) -> T::Amount {
+        let held = self.balance_on_hold(reason, account);
+        let actual = amount.min(held);
+        if actual.is_zero() {
+            return amount;
+        }
+
+        let new_held = held.checked_sub(&actual).unwrap();
+        if let Some(holds) = self.holds.get_mut(account) {
+            if new_held.is_zero() {
+                holds.remove(&reason);
+            } else {
+                holds.insert(reason, new_held);
+            }
+            if holds.is_empty() {
+                self.holds.remove(account);
+            }
+        }
+        self.deposit_event(Event::Released {
+            reason,
+            who: account.clone(),
+            amount: actual,
+        });
+
+        amount.checked_sub(&actual).unwrap()
+    }
+
+    // os holds ocupam uma parte do saldo livre, então nunca podem passar dele.
+    // operações que diminuem o saldo livre sem respeitar os holds (`slash` e `set_balance`)
+    // cortam os holds da conta, na ordem dos motivos, até caberem no novo saldo livre
+    fn cap_holds(&mut self, account: &T::AccountId) {
+        let free = self.get_balance(account);
+        let mut excess = match self.total_balance_on_hold(account).checked_sub(&free) {
+            Some(excess) if !excess.is_zero() => excess,
+            _ => return,
+        };
+
+        if let Some(holds) = self.holds.get_mut(account) {
+            for held in holds.values_mut() {
+                let cut = excess.min(*held);
+                *held = held.checked_sub(&cut).unwrap();
+                excess = excess.checked_sub(&cut).unwrap();
+            }
+            holds.retain(|_, held| !held.is_zero());
+            if holds.is_empty() {
+                self.holds.remove(account);
+            }
+        }
+    }
+
+    /// Valor em hold na conta com o motivo `reason`
+    pub fn balance_on_hold(&self, reason: HoldReason, account: &T::AccountId) -> T::Amount {
+        self.holds
+            .get(account)
+            .and_then(|holds| holds.get(&reason).copied())
+            .unwrap_or(T::Amount::zero())
+    }
+
+    /// Valor total em hold na conta: a soma dos holds de todos os motivos
+    pub fn total_balance_on_hold(&self, account: &T::AccountId) -> T::Amount {
+        self.holds
+            .get(account)
+            .map(|holds| {
+                holds.values().fold(T::Amount::zero(), |total, held| {
+                    total.checked_add(held).unwrap()
+                })
+            })
+            .unwrap_or(T::Amount::zero())
+    }
+
+    /// Valor travado na conta: o maior entre todos os locks
+    pub fn locked_balance(&self, account: &T::AccountId) -> T::Amount {
+        self.locks
+            .get(account)
+            .and_then(|locks| locks.values().max().copied())
+            .unwrap_or(T::Amount::zero())
+    }
+
+    /// Parte do saldo livre que não está travada por locks nem em hold, e pode ser movida
+    pub fn usable_balance(&self, account: &T::AccountId) -> T::Amount {
+        let untouchable = self
+            .locked_balance(account)
+            .max(self.total_balance_on_hold(account));
+        self.get_balance(account)
+            .checked_sub(&untouchable)
+            .unwrap_or(T::Amount::zero())
+    }
+
+    /// Quanto do saldo livre pode sair da conta: o que não está travado por locks nem em hold
+    /// e, com `keep_alive`, sem deixar a conta abaixo do depósito existencial
+    pub fn reducible_balance(&self, account: &T::AccountId, keep_alive: bool) -> T::Amount {
+        let mut untouchable = self
+            .locked_balance(account)
+            .max(self.total_balance_on_hold(account));
+        if keep_alive {
+            untouchable = untouchable.max(T::EXISTENTIAL_DEPOSIT);
+        }
+
+        self.get_balance(account)
+            .checked_sub(&untouchable)
+            .unwrap_or(T::Amount::zero())
+    }
+
+    // verifica se uma conta que vai receber fundos fica com pelo menos o depósito existencial.
+    // `new_free` é o saldo livre que a conta terá depois da operação
+    fn ensure_existential_deposit(
+        &self,
+        account: &T::AccountId,
+        new_free: T::Amount,
+    ) -> Result<(), &'static str> {
+        let new_total = new_free
+            .checked_add(&self.reserved_balance(account))
+            .ok_or(errors::BALANCE_OVERFLOW)?;
+        if new_total < T::EXISTENTIAL_DEPOSIT {
+            return Err(errors::BELOW_EXISTENTIAL_DEPOSIT);
+        }
+        Ok(())
+    }
+
+    // remove a conta se o saldo total dela ficou abaixo do depósito existencial.
+    // a poeira que sobrou é destruída, diminuindo o total_issuance.
+    // tudo o que é da conta também é apagado (locks, holds e as autorizações que ela deu),
+    // então, se ela receber fundos de novo, começa do zero. As autorizações que outras contas
+    // deram a ela pertencem a quem autorizou e continuam valendo. O congelamento é mantido: só o `Root` pode
+    // descongelar a conta, e ela não pode escapar dele queimando o saldo até virar poeira
+    fn reap_if_dust(&mut self, account: &T::AccountId) {
+        let dust = self.total_balance(account);
+        if dust >= T::EXISTENTIAL_DEPOSIT || !self.account_exists(account) {
+            return;
+        }
+
+        self.balance.remove(account);
+        self.reserved.remove(account);
+        self.holds.remove(account);
+        self.locks.remove(account);
+        self.allowances.retain(|(owner, _), _| owner != account);
+        self.total_issuance = self
+            .total_issuance
+            .checked_sub(&dust)
+            .expect("Total issuance underflow");
+        self.on_dust.on_dust(account, dust);
+        self.deposit_event(Event::Reaped {
+            who: account.clone(),
+            dust,
+        });
+    }
+
+    /// Retira os eventos emitidos desde a última chamada.
+    /// O runtime chama essa função ao final de cada extrinsic
+    pub fn take_events(&mut self) -> Vec<Event<T>> {
+        std::mem::take(&mut self.events)
+    }
+
+    fn deposit_event(&mut self, event: Event<T>) {
+        self.events.push(event);
+    }
+
+    /// Os ganchos `OnDeposit`, `OnWithdraw` e `OnDust` do pallet, para o runtime
+    /// consultar ou alterar o estado que eles guardam
+    pub fn hooks_mut(&mut self) -> (&mut T::OnDeposit, &mut T::OnWithdraw, &mut T::OnDust) {
+        (
+            &mut self.on_deposit,
+            &mut self.on_withdraw,
+            &mut self.on_dust,
+        )
+    }
+
+    /// Retorna se a conta existe, ou seja, se tem algum saldo livre ou reservado registrado
+    pub fn account_exists(&self, account: &T::AccountId) -> bool {
+        self.balance.contains_key(account) || self.reserved.contains_key(account)
+    }
+
+    /// Percorre os saldos livres de todas as contas, em ordem de `AccountId`
+    pub fn iter_balances(&self) -> impl Iterator<Item = (&T::AccountId, &T::Amount)> {
+        self.balance.iter()
+    }
+
+    /// Quantidade de contas existentes
+    pub fn account_count(&self) -> usize {
+        self.accounts().count()
+    }
+
+    /// As `n` contas com maior saldo total (livre + reservado), da maior para a menor.
+    /// Contas com o mesmo saldo ficam em ordem de `AccountId`
+    pub fn top_holders(&self, n: usize) -> Vec<(&T::AccountId, T::Amount)> {
+        let mut holders: Vec<_> = self
+            .accounts()
+            .map(|account| (account, self.total_balance(account)))
+            .collect();
+        holders.sort_by_key(|(_, total)| std::cmp::Reverse(*total));
+        holders.truncate(n);
+        holders
+    }
+
+    // todas as contas existentes: as que têm saldo livre e as que só têm saldo reservado
+    fn accounts(&self) -> impl Iterator<Item = &T::AccountId> {
+        self.balance.keys().chain(
+            self.reserved
+                .keys()
+                .filter(|account| !self.balance.contains_key(*account)),
+        )
+    }
+
+    /// Transfere `amount` de `caller` para `to` com todas as verificações de `transfer`,
+    /// menos a do valor mínimo. Usada por `force_transfer` e pela cobrança de taxas
+    pub fn do_transfer(
+        &mut self,
+        caller: T::AccountId,
+        to: T::AccountId,
+        amount: T::Amount,
+    ) -> Result<(), &'static str> {
+        // contas congeladas não enviam nem recebem transferências
+        self.ensure_not_frozen(&caller)?;
+        self.ensure_not_frozen(&to)?;
+
+        // recupero o saldo de quem está querendo transferir
+        let caller_balance = self.get_balance(&caller);
+
+        // recupero o saldo para quem vai o 'amount'
+        let to_balance = self.get_balance(&to);
+
+        // novo saldo de quem quer fazer a transferência
+        // subtraindo o valor do saldo existente.
+        // importante é que devemos verificar se o caller_balance
+        // tem saldo, caso sim, o resultado é ok, caso contrário
+        // lançamos um erro estático: 'Insufficient balance'
+        let new_caller_balance = caller_balance
+            .checked_sub(&amount)
+            .ok_or(errors::INSUFFICIENT_BALANCE)?;
+
+        // o saldo que sobra precisa cobrir os locks da conta
+        self.ensure_can_withdraw(&caller, new_caller_balance)?;
+
+        // novo saldo de quem vai receber o 'amount'
+        let new_to_balance = to_balance
+            .checked_add(&amount)
+            .ok_or(errors::BALANCE_OVERFLOW)?;
+
+        // transferir para si mesmo não muda nada, mas só depois de
+        // garantirmos que o `caller` tem saldo suficiente
+        if caller == to {
+            return Ok(());
+        }
+
+        // quem recebe precisa ficar com pelo menos o depósito existencial
+        self.ensure_existential_deposit(&to, new_to_balance)?;
+
+        // agora atualizamos os saldos.
+        // uma transferência apenas move fundos, então o total_issuance não muda
+        self.write_balance(&caller, new_caller_balance);
+        self.write_balance(&to, new_to_balance);
+        self.on_withdraw.on_withdraw(&caller, amount);
+        self.on_deposit.on_deposit(&to, amount);
+        self.deposit_event(Event::Transfer {
+            from: caller.clone(),
+            to,
+            amount,
+        });
+
+        // se o `caller` ficou só com poeira, a conta é removida
+        self.reap_if_dust(&caller);
+
+        // tudo certo
+        Ok(())
+    }
+
+    // falha se `amount` for menor que o valor mínimo de uma transferência
+    fn ensure_minimum_transfer(&self, amount: T::Amount) -> Result<(), &'static str> {
+        if amount < T::MinimumTransfer::get() {
+            return Err(errors::BELOW_MINIMUM);
+        }
+        Ok(())
+    }
+
+    // aplica a taxa de `accrual` ao saldo livre de cada conta, emitindo `Minted` ou `Burned`.
+    // a demurrage só incide sobre o que a conta pode movimentar: a parte travada por locks
+    // ou em hold não diminui, então o saldo livre nunca fica abaixo dela.
+    // uma conta cujo valor não cabe no tipo `Amount` (overflow) fica como está
+    fn apply_accrual(&mut self, accrual: Accrual) {
+        let (rate, interest) = match accrual {
+            Accrual::Interest(rate) => (rate, true),
+            Accrual::Demurrage(rate) => (rate, false),
+        };
+        let million = T::Amount::from(1_000_000);
+
+        // calculamos todas as variações antes, já que a parte travada depende de outros mapas
+        let deltas: Vec<(T::AccountId, T::Amount)> = self
+            .balance
+            .keys()
+            .filter_map(|account| {
+                let base = if interest {
+                    self.get_balance(account)
+                } else {
+                    self.reducible_balance(account, false)
+                };
+                base.checked_mul(&T::Amount::from(rate))
+                    .and_then(|value| value.checked_div(&million))
+                    .filter(|delta| !delta.is_zero())
+                    .map(|delta| (account.clone(), delta))
+            })
+            .collect();
+
+        for (account, delta) in deltas {
+            let balance = self.get_balance(&account);
+            if interest {
+                let (Some(new_balance), Some(new_total_issuance)) = (
+                    balance.checked_add(&delta),
+                    self.total_issuance.checked_add(&delta),
+                ) else {
+                    continue;
+                };
+                self.write_balance(&account, new_balance);
+                self.total_issuance = new_total_issuance;
+                self.on_deposit.on_deposit(&account, delta);
+                self.deposit_event(Event::Minted {
+                    who: account,
+                    amount: delta,
+                });
+            } else {
+                // `delta` sai da parte livre e sem travas, então cabe no saldo
+                self.write_balance(&account, balance - delta);
+                self.total_issuance = self
+                    .total_issuance
+                    .checked_sub(&delta)
+                    .expect("Total issuance underflow");
+                self.on_withdraw.on_withdraw(&account, delta);
+                self.deposit_event(Event::Burned {
+                    who: account.clone(),
+                    amount: delta,
+                });
+
+                // a demurrage pode deixar a conta só com poeira
+                self.reap_if_dust(&account);
+            }
+        }
+    }
+
+    /// Retorna se a conta está congelada
+    pub fn is_frozen(&self, account: &T::AccountId) -> bool {
+        self.frozen.contains_key(account)
+    }
+
+    // falha se a conta estiver congelada
+    fn ensure_not_frozen(&self, account: &T::AccountId) -> Result<(), &'static str> {
+        if self.is_frozen(account) {
+            return Err(errors::ACCOUNT_FROZEN);
+        }
+        Ok(())
+    }
+
+    // verifica se a conta pode ficar com `new_free` de saldo livre sem violar seus locks
+    fn ensure_can_withdraw(
+        &self,
+        account: &T::AccountId,
+        new_free: T::Amount,
+    ) -> Result<(), &'static str> {
+        if new_free < self.locked_balance(account) {
+            return Err(errors::BALANCE_LOCKED);
+        }
+        if new_free < self.total_balance_on_hold(account) {
+            return Err(errors::BALANCE_ON_HOLD);
+        }
+        Ok(())
+    }
+
+    // escreve o saldo reservado da conta, removendo a entrada quando chega a zero
+    fn write_reserved(&mut self, account: &T::AccountId, amount: T::Amount) {
+        if amount.is_zero() {
+            self.reserved.remove(account);
+            return;
+        }
+
+        match self.reserved.get_mut(account) {
+            Some(reserved) => *reserved = amount,
+            None => {
+                self.reserved.insert(account.clone(), amount);
+            }
+        }
+    }
+
+    /// Saldo livre da conta, ou seja, o que pode ser transferido
+    pub fn get_balance(&self, account: &T::AccountId) -> T::Amount {
+        // Aqui podemos pegar o saldo de uma carteira se ela existir,
+        // caso contrário retorna zero
+        //! note que tem o '*' no início, o que significa que é um
+        //! ponteiro para o própria instância de balance (&self)
+        *self.balance.get(account).unwrap_or(&T::Amount::zero())
+    }
+}
+
+/// Estado inicial (gênesis) do pallet: as contas que já nascem com saldo
+pub struct GenesisConfig<T: Config> {
+    /// Contas e seus saldos iniciais. Cada conta só pode aparecer uma vez
+    pub balances: Vec<(T::AccountId, T::Amount)>,
+}
+
+// as contas citadas em cada evento, para o índice de eventos por conta do system
+impl<T: Config> EventTopics<T::AccountId> for Event<T> {
+    fn accounts(&self) -> Vec<&T::AccountId> {
+        match self {
+            Event::Transfer { from, to, .. }
+            | Event::ReserveRepatriated { from, to, .. }
+            | Event::Memo { from, to, .. } => vec![from, to],
+            Event::Approval { owner, spender, .. } => vec![owner, spender],
+            Event::BalanceSet { who, .. }
+            | Event::Minted { who, .. }
+            | Event::Burned { who, .. }
+            | Event::Reserved { who, .. }
+            | Event::Unreserved { who, .. }
+            | Event::Slashed { who, .. }
+            | Event::Reaped { who, .. }
+            | Event::Frozen { who }
+            | Event::Thawed { who }
+            | Event::Held { who, .. }
+            | Event::Released { who, .. } => vec![who],
+        }
+    }
+}
+
+// ao final de cada bloco, aplicamos os juros ou a demurrage configurados
+impl<T: Config, BlockNumber> Hooks<BlockNumber> for Pallet<T> {
+    fn on_finalize(&mut self, _block_number: BlockNumber) {
+        if let Some(accrual) = T::ACCRUAL {
+            self.apply_accrual(accrual);
+        }
+    }
+
+    // o total em circulação precisa ser sempre a soma dos saldos livres e reservados
+    fn try_state(&self) -> Result<(), &'static str> {
+        let sum = self
+            .balance
+            .values()
+            .chain(self.reserved.values())
+            .try_fold(T::Amount::zero(), |sum, amount| sum.checked_add(amount))
+            .ok_or("Overflow when adding up all balances")?;
+        if sum != self.total_issuance {
+            return Err("Total issuance does not match the sum of all balances");
+        }
+        if self
+            .holds
+            .keys()
+            .any(|account| self.total_balance_on_hold(account) > self.get_balance(account))
+        {
+            return Err("Balance on hold exceeds free balance");
+        }
+        Ok(())
+    }
+}
+
+// o runtime abre uma transação antes de cada dispatch, para desfazer uma chamada que entrar em pânico.
+// os eventos não precisam ser guardados: os de uma extrinsic que falhou são descartados
+impl<T: Config> Transactional for Pallet<T> {
+    fn begin(&mut self) {
+        self.balance.begin();
+        self.reserved.begin();
+        self.locks.begin();
+        self.holds.begin();
+        self.allowances.begin();
+        self.frozen.begin();
+        self.checkpoint = Some((
+            self.total_issuance,
+            self.on_deposit.clone(),
+            self.on_withdraw.clone(),
+            self.on_dust.clone(),
+        ));
+    }
+
+    fn commit(&mut self) {
+        self.balance.commit();
+        self.reserved.commit();
+        self.locks.commit();
+        self.holds.commit();
+        self.allowances.commit();
+        self.frozen.commit();
+        self.checkpoint = None;
+    }
+
+    fn rollback(&mut self) {
+        self.balance.rollback();
+        self.reserved.rollback();
+        self.locks.rollback();
+        self.holds.rollback();
+        self.allowances.rollback();
+        self.frozen.rollback();
+        if let Some((total_issuance, on_deposit, on_withdraw, on_dust)) = self.checkpoint.take() {
+            self.total_issuance = total_issuance;
+            self.on_deposit = on_deposit;
+            self.on_withdraw = on_withdraw;
+            self.on_dust = on_dust;
+        }
+    }
+}
+
+// o balances é a moeda nativa do runtime, usada pelos outros pallets através de `Currency`
+impl<T: Config> Currency<T::AccountId, T::Amount> for Pallet<T> {
+    fn free_balance(&self, who: &T::AccountId) -> T::Amount {
+        self.get_balance(who)
+    }
+
+    fn total_issuance(&self) -> T::Amount {
+        self.total_issuance
+    }
+
+    // sem a verificação do valor mínimo, que só vale para as transferências dos usuários
+    fn transfer(
+        &mut self,
+        from: &T::AccountId,
+        to: &T::AccountId,
+        amount: T::Amount,
+    ) -> DispatchResult {
+        self.do_transfer(from.clone(), to.clone(), amount)
+    }
+
+    fn withdraw(&mut self, who: &T::AccountId, amount: T::Amount) -> DispatchResult {
+        Pallet::withdraw(self, who, amount)
+    }
+
+    fn deposit(&mut self, who: &T::AccountId, amount: T::Amount) -> DispatchResult {
+        Pallet::deposit(self, who, amount)
+    }
+
+    fn reserve(&mut self, who: &T::AccountId, amount: T::Amount) -> DispatchResult {
+        Pallet::reserve(self, who, amount)
+    }
+
+    fn unreserve(&mut self, who: &T::AccountId, amount: T::Amount) -> T::Amount {
+        Pallet::unreserve(self, who, amount)
+    }
+
+    fn slash(&mut self, who: &T::AccountId, amount: T::Amount) -> (T::Amount, T::Amount) {
+        Pallet::slash(self, who, amount)
+    }
+}
+
+impl<T: Config> GenesisConfig<T> {
+    /// Constrói o pallet a partir da configuração de gênesis.
+    /// Falha se uma conta aparecer mais de uma vez ou com saldo abaixo do depósito existencial
+    pub fn build(&self) -> Result<Pallet<T>, &'static str> {
+        let mut accounts = BTreeSet::new();
+        for (account, amount) in &self.balances {
+            if !accounts.insert(account) {
+                return Err("Duplicate account in genesis balances");
+            }
+            if *amount < T::EXISTENTIAL_DEPOSIT {
+                return Err(errors::BELOW_EXISTENTIAL_DEPOSIT);
+            }
+        }
+
+        let mut pallet = Pallet::new();
+        for (account, amount) in &self.balances {
+            pallet.set_balance(account, *amount)?;
+        }
+        Ok(pallet)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Event;
+    use crate::support::{ConstU32, Origin, PalletId};
+
+    #[derive(Debug, PartialEq)]
+    struct TestConfig;
+
+    impl super::Config for TestConfig {
+        type AccountId = String;
+        type Amount = u32;
+        const EXISTENTIAL_DEPOSIT: u32 = 10;
+        type MinimumTransfer = ConstU32<5>;
+        const MAX_MEMO_LENGTH: u32 = 8;
+        const ACCRUAL: Option<super::Accrual> = None;
+        type OnDeposit = ();
+        type OnWithdraw = ();
+        type OnDust = ();
+    }
+
+    #[test]
+    fn init_balances() {
+        let mut balances: super::Pallet<TestConfig> = super::Pallet::new();
+        balances.set_balance(&"Lucio".to_string(), 100).unwrap();
+        balances.set_balance(&"Miriam".to_string(), 300).unwrap();
+    }
+
+    #[test]
+    fn success_tranfer() {
+        // instanciamos o Pallet de balances
+        let mut balances: super::Pallet<TestConfig> = super::Pallet::new();
+
+        // defino os usuários (account)
+        let miriam = "Miriam".to_string();
+        let lucio = "Lucio".to_string();
+
+        // definimos os valores iniciais de cada conta
+        balances.set_balance(&miriam, 200).unwrap();
+        balances.set_balance(&lucio, 100).unwrap();
+
+        // '_' para ignorar o retorno do 'transfer'
+        // miriam transfere 50 para o lucio
+        let _ = balances.transfer(miriam.clone(), lucio.clone(), 50);
+
+        // miriam tem agora 150?
+        assert_eq!(balances.get_balance(&miriam), 150);
+
+        // lucio agora tem 150?
+        assert_eq!(balances.get_balance(&lucio), 150);
+
+        // a transferência não cria nem destrói moedas
+        assert_eq!(balances.total_issuance(), 300);
+    }
+
+    #[test]
+    fn transfer_to_self() {
+        let mut balances: super::Pallet<TestConfig> = super::Pallet::new();
+        let miriam = "Miriam".to_string();
+        balances.set_balance(&miriam, 100).unwrap();
+
+        // transferir para si mesmo não pode criar saldo
+        let result = balances.transfer(miriam.clone(), miriam.clone(), 50);
+        assert_eq!(result, Ok(()));
+        assert_eq!(balances.get_balance(&miriam), 100);
+        assert_eq!(balances.total_issuance(), 100);
+
+        // mas ainda exige saldo suficiente
+        let result = balances.transfer(miriam.clone(), miriam.clone(), 150);
+        assert_eq!(result, Err("Insufficient balance"));
+    }
+
+    #[test]
+    fn insufficient_balance() {
+        // instanciamos o Pallet de balances
+        let mut balances: super::Pallet<TestConfig> = super::Pallet::new();
+
+        // defino os usuários (account)
+        let caller: String = "Lucio".to_string();
+        let to: String = "Miriam".to_string();
+
+        // defino o saldo da miriam para 1500
+        balances.set_balance(&caller, 1500).unwrap();
 
         // tento transferir 2000 da miriam para o lucio
         let result = balances.transfer(caller.clone(), to.clone(), 2000);
 
         assert_eq!(result, Err("Insufficient balance"));
     }
+
+    #[test]
+    fn set_balance_tracks_total_issuance() {
+        let mut balances: super::Pallet<TestConfig> = super::Pallet::new();
+        balances.set_balance(&"Lucio".to_string(), 100).unwrap();
+        balances.set_balance(&"Miriam".to_string(), 300).unwrap();
+        assert_eq!(balances.total_issuance(), 400);
+
+        // sobrescrever um saldo substitui o valor antigo no total
+        balances.set_balance(&"Miriam".to_string(), 50).unwrap();
+        assert_eq!(balances.total_issuance(), 150);
+    }
+
+    #[test]
+    fn mint_and_burn() {
+        let mut balances: super::Pallet<TestConfig> = super::Pallet::new();
+        let lucio = "Lucio".to_string();
+
+        // apenas a origem `Root` pode criar moedas
+        let result = balances.mint(Origin::Signed(lucio.clone()), lucio.clone(), 100);
+        assert_eq!(result, Err("BadOrigin"));
+
+        // criamos 100 moedas para o lucio
+        let result = balances.mint(Origin::Root, lucio.clone(), 100);
+        assert_eq!(result, Ok(()));
+        assert_eq!(balances.get_balance(&lucio), 100);
+        assert_eq!(balances.total_issuance(), 100);
+
+        // destruímos 30 moedas do lucio
+        let result = balances.burn(Origin::Root, lucio.clone(), 30);
+        assert_eq!(result, Ok(()));
+        assert_eq!(balances.get_balance(&lucio), 70);
+        assert_eq!(balances.total_issuance(), 70);
+
+        // não é possível destruir mais do que o saldo da conta
+        let result = balances.burn(Origin::Root, lucio.clone(), 71);
+        assert_eq!(result, Err("Insufficient balance"));
+        assert_eq!(balances.total_issuance(), 70);
+
+        // nem criar moedas além do limite do tipo `Amount`
+        let result = balances.mint(Origin::Root, lucio.clone(), u32::MAX);
+        assert_eq!(result, Err("Overflow when adding to balance"));
+        assert_eq!(balances.total_issuance(), 70);
+    }
+
+    #[test]
+    fn reserve_and_unreserve() {
+        let mut balances: super::Pallet<TestConfig> = super::Pallet::new();
+        let lucio = "Lucio".to_string();
+        let miriam = "Miriam".to_string();
+        balances.set_balance(&lucio, 100).unwrap();
+
+        // reservamos 60 do lucio
+        assert_eq!(balances.reserve(&lucio, 60), Ok(()));
+        assert_eq!(balances.get_balance(&lucio), 40);
+        assert_eq!(balances.reserved_balance(&lucio), 60);
+        assert_eq!(balances.total_balance(&lucio), 100);
+
+        // não é possível reservar mais do que o saldo livre
+        assert_eq!(balances.reserve(&lucio, 50), Err("Insufficient balance"));
+
+        // a transferência só pode usar o saldo livre
+        let result = balances.transfer(lucio.clone(), miriam.clone(), 50);
+        assert_eq!(result, Err("Insufficient balance"));
+
+        // liberamos 80, mas só havia 60 reservados: sobram 20 não liberados
+        assert_eq!(balances.unreserve(&lucio, 80), 20);
+        assert_eq!(balances.get_balance(&lucio), 100);
+        assert_eq!(balances.reserved_balance(&lucio), 0);
+
+        // reservar e liberar não muda o total em circulação
+        assert_eq!(balances.total_issuance(), 100);
+    }
+
+    #[test]
+    fn slash_and_repatriate_reserved() {
+        let mut balances: super::Pallet<TestConfig> = super::Pallet::new();
+        let lucio = "Lucio".to_string();
+        let miriam = "Miriam".to_string();
+        balances.set_balance(&lucio, 100).unwrap();
+        balances.reserve(&lucio, 80).unwrap();
+
+        // cortamos 30 da reserva do lucio: essas moedas deixam de existir
+        assert_eq!(balances.slash_reserved(&lucio, 30), 0);
+        assert_eq!(balances.reserved_balance(&lucio), 50);
+        assert_eq!(balances.total_issuance(), 70);
+
+        // movemos a reserva restante (e tentamos mais 10) para a miriam
+        assert_eq!(balances.repatriate_reserved(&lucio, &miriam, 60), Ok(10));
+        assert_eq!(balances.reserved_balance(&lucio), 0);
+        assert_eq!(balances.get_balance(&lucio), 20);
+        assert_eq!(balances.get_balance(&miriam), 50);
+        assert_eq!(balances.total_issuance(), 70);
+
+        // não há mais nada reservado para cortar
+        assert_eq!(balances.slash_reserved(&lucio, 5), 5);
+    }
+
+    #[test]
+    fn locks_overlap_and_block_transfers() {
+        let mut balances: super::Pallet<TestConfig> = super::Pallet::new();
+        let lucio = "Lucio".to_string();
+        let miriam = "Miriam".to_string();
+        balances.set_balance(&lucio, 100).unwrap();
+
+        // dois locks na mesma conta: vale o maior, não a soma
+        balances.set_lock(*b"staking ", &lucio, 60);
+        balances.set_lock(*b"vesting ", &lucio, 30);
+        assert_eq!(balances.locked_balance(&lucio), 60);
+        assert_eq!(balances.usable_balance(&lucio), 40);
+
+        // não podemos transferir fundos travados
+        let result = balances.transfer(lucio.clone(), miriam.clone(), 50);
+        assert_eq!(result, Err("Balance is locked"));
+        assert_eq!(balances.reserve(&lucio, 50), Err("Balance is locked"));
+
+        // mas podemos transferir a parte livre de locks
+        let result = balances.transfer(lucio.clone(), miriam.clone(), 40);
+        assert_eq!(result, Ok(()));
+
+        // removendo o lock maior, passa a valer o de 30
+        balances.remove_lock(*b"staking ", &lucio);
+        assert_eq!(balances.locked_balance(&lucio), 30);
+        assert_eq!(balances.usable_balance(&lucio), 30);
+
+        // atualizar um lock existente substitui o valor anterior
+        balances.set_lock(*b"vesting ", &lucio, 10);
+        assert_eq!(balances.locked_balance(&lucio), 10);
+
+        balances.remove_lock(*b"vesting ", &lucio);
+        assert_eq!(balances.locked_balance(&lucio), 0);
+    }
+
+    #[test]
+    fn approve_and_transfer_from() {
+        let mut balances: super::Pallet<TestConfig> = super::Pallet::new();
+        let lucio = "Lucio".to_string();
+        let miriam = "Miriam".to_string();
+        let exchange = "Exchange".to_string();
+        balances.set_balance(&lucio, 100).unwrap();
+
+        // sem aprovação, a exchange não pode mover os fundos do lucio
+        let result = balances.transfer_from(exchange.clone(), lucio.clone(), miriam.clone(), 10);
+        assert_eq!(result, Err("Insufficient allowance"));
+
+        // lucio autoriza a exchange a gastar até 50
+        assert_eq!(
+            balances.approve(lucio.clone(), exchange.clone(), 50),
+            Ok(())
+        );
+        assert_eq!(balances.allowance(&lucio, &exchange), 50);
+
+        // a exchange move 30 do lucio para a miriam
+        let result = balances.transfer_from(exchange.clone(), lucio.clone(), miriam.clone(), 30);
+        assert_eq!(result, Ok(()));
+        assert_eq!(balances.get_balance(&lucio), 70);
+        assert_eq!(balances.get_balance(&miriam), 30);
+        assert_eq!(balances.allowance(&lucio, &exchange), 20);
+
+        // não pode passar do que sobrou da autorização
+        let result = balances.transfer_from(exchange.clone(), lucio.clone(), miriam.clone(), 30);
+        assert_eq!(result, Err("Insufficient allowance"));
+
+        // se a transferência falhar, a autorização não é consumida
+        balances
+            .approve(lucio.clone(), exchange.clone(), 500)
+            .unwrap();
+        let result = balances.transfer_from(exchange.clone(), lucio.clone(), miriam.clone(), 200);
+        assert_eq!(result, Err("Insufficient balance"));
+        assert_eq!(balances.allowance(&lucio, &exchange), 500);
+
+        // aprovar zero remove a autorização
+        balances
+            .approve(lucio.clone(), exchange.clone(), 0)
+            .unwrap();
+        assert_eq!(balances.allowance(&lucio, &exchange), 0);
+    }
+
+    #[test]
+    fn existential_deposit_and_reaping() {
+        let mut balances: super::Pallet<TestConfig> = super::Pallet::new();
+        let lucio = "Lucio".to_string();
+        let miriam = "Miriam".to_string();
+        balances.set_balance(&lucio, 100).unwrap();
+
+        // não é possível criar uma conta com menos que o depósito existencial
+        let result = balances.transfer(lucio.clone(), miriam.clone(), 5);
+        assert_eq!(result, Err("Balance below existential deposit"));
+        assert!(!balances.account_exists(&miriam));
+
+        // lucio transfere 95 e fica com 5 de poeira: a conta é removida
+        let result = balances.transfer(lucio.clone(), miriam.clone(), 95);
+        assert_eq!(result, Ok(()));
+        assert!(!balances.account_exists(&lucio));
+        assert_eq!(balances.get_balance(&lucio), 0);
+
+        // a poeira é destruída
+        assert_eq!(balances.total_issuance(), 95);
+
+        // um saldo reservado também mantém a conta viva
+        balances.reserve(&miriam, 50).unwrap();
+        let result = balances.transfer(miriam.clone(), lucio.clone(), 40);
+        assert_eq!(result, Ok(()));
+        assert!(balances.account_exists(&miriam));
+        assert_eq!(balances.total_balance(&miriam), 55);
+
+        // uma conta removida perde os locks e as autorizações que deu, mas continua
+        // congelada e mantém as autorizações que recebeu de outras contas
+        balances.unreserve(&miriam, 50);
+        balances.set_lock(*b"staking ", &miriam, 30);
+        balances.approve(miriam.clone(), lucio.clone(), 20).unwrap();
+        balances.approve(lucio.clone(), miriam.clone(), 20).unwrap();
+        balances.freeze(Origin::Root, miriam.clone()).unwrap();
+        balances.slash(&miriam, 50);
+        assert!(!balances.account_exists(&miriam));
+        assert!(balances.is_frozen(&miriam));
+        assert_eq!(balances.locked_balance(&miriam), 0);
+        assert_eq!(balances.allowance(&miriam, &lucio), 0);
+        assert_eq!(balances.allowance(&lucio, &miriam), 20);
+
+        // e, quando recebe fundos de novo, o lock antigo não volta a valer,
+        // mas ela só movimenta o saldo depois que o `Root` a descongelar
+        balances.set_balance(&miriam, 40).unwrap();
+        let result = balances.transfer(miriam.clone(), lucio.clone(), 40);
+        assert_eq!(result, Err("Account is frozen"));
+        balances.thaw(Origin::Root, miriam.clone()).unwrap();
+        let result = balances.transfer(miriam.clone(), lucio.clone(), 40);
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn transfer_all() {
+        let mut balances: super::Pallet<TestConfig> = super::Pallet::new();
+        let lucio = "Lucio".to_string();
+        let miriam = "Miriam".to_string();
+        balances.set_balance(&lucio, 100).unwrap();
+        balances.reserve(&lucio, 20).unwrap();
+        balances.set_lock(*b"staking ", &lucio, 30);
+
+        // com keep_alive, sobra o maior entre o lock (30) e o depósito existencial (10)
+        assert_eq!(balances.reducible_balance(&lucio, true), 50);
+        let result = balances.transfer_all(lucio.clone(), miriam.clone(), true);
+        assert_eq!(result, Ok(()));
+        assert_eq!(balances.get_balance(&lucio), 30);
+        assert_eq!(balances.reserved_balance(&lucio), 20);
+        assert_eq!(balances.get_balance(&miriam), 50);
+
+        // sem locks e sem keep_alive, a conta é esvaziada e removida
+        let result = balances.transfer_all(miriam.clone(), lucio.clone(), false);
+        assert_eq!(result, Ok(()));
+        assert!(!balances.account_exists(&miriam));
+        assert_eq!(balances.get_balance(&lucio), 80);
+
+        // com keep_alive e sem locks, fica exatamente o depósito existencial
+        balances.remove_lock(*b"staking ", &lucio);
+        let result = balances.transfer_all(lucio.clone(), miriam.clone(), true);
+        assert_eq!(result, Ok(()));
+        assert_eq!(balances.get_balance(&lucio), 10);
+        assert_eq!(balances.get_balance(&miriam), 70);
+        assert_eq!(balances.total_issuance(), 100);
+
+        // um lock menor que o depósito existencial não é destruído sem keep_alive:
+        // a conta fica com o depósito existencial e continua existindo
+        balances.set_lock(*b"vesting ", &miriam, 5);
+        let result = balances.transfer_all(miriam.clone(), lucio.clone(), false);
+        assert_eq!(result, Ok(()));
+        assert!(balances.account_exists(&miriam));
+        assert_eq!(balances.get_balance(&miriam), 10);
+        assert_eq!(balances.locked_balance(&miriam), 5);
+        assert_eq!(balances.get_balance(&lucio), 70);
+        assert_eq!(balances.total_issuance(), 100);
+    }
+
+    #[test]
+    fn batch_transfer() {
+        let mut balances: super::Pallet<TestConfig> = super::Pallet::new();
+        let lucio = "Lucio".to_string();
+        let miriam = "Miriam".to_string();
+        let pedro = "Pedro".to_string();
+        balances.set_balance(&lucio, 100).unwrap();
+
+        // lucio paga a miriam e o pedro de uma vez
+        let result = balances.batch_transfer(
+            lucio.clone(),
+            vec![(miriam.clone(), 30), (pedro.clone(), 20)],
+        );
+        assert_eq!(result, Ok(()));
+        assert_eq!(balances.get_balance(&lucio), 50);
+        assert_eq!(balances.get_balance(&miriam), 30);
+        assert_eq!(balances.get_balance(&pedro), 20);
+        assert_eq!(balances.total_issuance(), 100);
+    }
+
+    #[test]
+    fn batch_transfer_insufficient_funds_mid_batch() {
+        let mut balances: super::Pallet<TestConfig> = super::Pallet::new();
+        let lucio = "Lucio".to_string();
+        let miriam = "Miriam".to_string();
+        let pedro = "Pedro".to_string();
+        balances.set_balance(&lucio, 100).unwrap();
+
+        // a primeira transferência caberia no saldo, mas a segunda não:
+        // nada pode ser transferido
+        let result = balances.batch_transfer(
+            lucio.clone(),
+            vec![(miriam.clone(), 60), (pedro.clone(), 60)],
+        );
+        assert_eq!(result, Err("Insufficient balance"));
+        assert_eq!(balances.get_balance(&lucio), 100);
+        assert_eq!(balances.get_balance(&miriam), 0);
+        assert_eq!(balances.get_balance(&pedro), 0);
+
+        // se o crédito de uma transferência do meio falharia, nenhuma acontece
+        let result = balances.batch_transfer(
+            lucio.clone(),
+            vec![(miriam.clone(), 60), (pedro.clone(), 5)],
+        );
+        assert_eq!(result, Err("Balance below existential deposit"));
+        assert_eq!(balances.get_balance(&lucio), 100);
+        assert_eq!(balances.get_balance(&miriam), 0);
+        assert_eq!(balances.total_issuance(), 100);
+    }
+
+    #[test]
+    fn batch_transfer_to_self_and_repeated_recipients() {
+        let mut balances: super::Pallet<TestConfig> = super::Pallet::new();
+        let lucio = "Lucio".to_string();
+        let miriam = "Miriam".to_string();
+        balances.set_balance(&lucio, 100).unwrap();
+
+        // a parte que o lucio manda para si mesmo não sai da conta e não gera evento
+        let result = balances.batch_transfer(
+            lucio.clone(),
+            vec![(lucio.clone(), 40), (miriam.clone(), 30)],
+        );
+        assert_eq!(result, Ok(()));
+        assert_eq!(balances.get_balance(&lucio), 70);
+        assert_eq!(balances.get_balance(&miriam), 30);
+        assert_eq!(
+            balances.take_events(),
+            vec![Event::Transfer {
+                from: lucio.clone(),
+                to: miriam.clone(),
+                amount: 30
+            }]
+        );
+
+        // mesmo assim, o total do lote precisa caber no saldo, como no `do_transfer`
+        let result = balances.batch_transfer(
+            lucio.clone(),
+            vec![(lucio.clone(), 60), (miriam.clone(), 20)],
+        );
+        assert_eq!(result, Err("Insufficient balance"));
+
+        // créditos repetidos para a mesma conta são somados antes da verificação:
+        // a pedro recebe 5 + 5, o que cobre o depósito existencial
+        let pedro = "Pedro".to_string();
+        let result =
+            balances.batch_transfer(lucio.clone(), vec![(pedro.clone(), 5), (pedro.clone(), 5)]);
+        assert_eq!(result, Ok(()));
+        assert_eq!(balances.get_balance(&pedro), 10);
+        assert_eq!(balances.get_balance(&lucio), 60);
+        assert_eq!(balances.total_issuance(), 100);
+    }
+
+    #[test]
+    fn force_calls_require_root() {
+        let mut balances: super::Pallet<TestConfig> = super::Pallet::new();
+        let lucio = "Lucio".to_string();
+        let miriam = "Miriam".to_string();
+        balances.set_balance(&lucio, 100).unwrap();
+
+        // contas assinadas não podem usar as chamadas `force_*`
+        let result = balances.force_transfer(
+            Origin::Signed(miriam.clone()),
+            lucio.clone(),
+            miriam.clone(),
+            50,
+        );
+        assert_eq!(result, Err("BadOrigin"));
+        let result = balances.force_set_balance(Origin::Signed(miriam.clone()), miriam.clone(), 50);
+        assert_eq!(result, Err("BadOrigin"));
+        assert_eq!(balances.get_balance(&miriam), 0);
+
+        // com a origem `Root`, movemos fundos do lucio sem a assinatura dele
+        let result = balances.force_transfer(Origin::Root, lucio.clone(), miriam.clone(), 50);
+        assert_eq!(result, Ok(()));
+        assert_eq!(balances.get_balance(&lucio), 50);
+        assert_eq!(balances.get_balance(&miriam), 50);
+
+        // e definimos o saldo da miriam diretamente
+        let result = balances.force_set_balance(Origin::Root, miriam.clone(), 200);
+        assert_eq!(result, Ok(()));
+        assert_eq!(balances.get_balance(&miriam), 200);
+        assert_eq!(balances.total_issuance(), 250);
+
+        // um saldo que estouraria o total em circulação é recusado, sem mudar nada
+        let result = balances.force_set_balance(Origin::Root, miriam.clone(), u32::MAX);
+        assert_eq!(result, Err("Total issuance overflow"));
+        assert_eq!(balances.get_balance(&miriam), 200);
+        assert_eq!(balances.total_issuance(), 250);
+    }
+
+    #[test]
+    fn events_are_deposited() {
+        let mut balances: super::Pallet<TestConfig> = super::Pallet::new();
+        let lucio = "Lucio".to_string();
+        let miriam = "Miriam".to_string();
+
+        // o set_balance é usado na gênese e não emite eventos
+        balances.set_balance(&lucio, 100).unwrap();
+        assert!(balances.take_events().is_empty());
+
+        // lucio transfere quase tudo e a poeira que sobra é removida
+        balances
+            .transfer(lucio.clone(), miriam.clone(), 95)
+            .unwrap();
+        assert_eq!(
+            balances.take_events(),
+            vec![
+                Event::Transfer {
+                    from: lucio.clone(),
+                    to: miriam.clone(),
+                    amount: 95
+                },
+                Event::Reaped {
+                    who: lucio.clone(),
+                    dust: 5
+                },
+            ]
+        );
+
+        // os eventos são retirados apenas uma vez
+        assert!(balances.take_events().is_empty());
+
+        // um lote que falha não deixa eventos para trás
+        let result = balances.batch_transfer(
+            miriam.clone(),
+            vec![(lucio.clone(), 50), (lucio.clone(), 5)],
+        );
+        assert_eq!(result, Ok(()));
+        let result = balances.batch_transfer(
+            miriam.clone(),
+            vec![(lucio.clone(), 10), (miriam.clone(), 100)],
+        );
+        assert_eq!(result, Err("Insufficient balance"));
+        let result = balances.batch_transfer(
+            miriam.clone(),
+            vec![(lucio.clone(), 10), ("Pedro".to_string(), 5)],
+        );
+        assert_eq!(result, Err("Balance below existential deposit"));
+        assert_eq!(balances.take_events().len(), 2);
+    }
+
+    #[test]
+    fn genesis_config() {
+        let genesis = super::GenesisConfig::<TestConfig> {
+            balances: vec![("Miriam".to_string(), 100), ("Lucio".to_string(), 50)],
+        };
+        let balances = genesis.build().unwrap();
+        assert_eq!(balances.get_balance(&"Miriam".to_string()), 100);
+        assert_eq!(balances.get_balance(&"Lucio".to_string()), 50);
+        assert_eq!(balances.total_issuance(), 150);
+
+        // cada conta só pode aparecer uma vez
+        let genesis = super::GenesisConfig::<TestConfig> {
+            balances: vec![("Miriam".to_string(), 100), ("Miriam".to_string(), 50)],
+        };
+        assert_eq!(
+            genesis.build().err(),
+            Some("Duplicate account in genesis balances")
+        );
+
+        // nenhuma conta pode nascer abaixo do depósito existencial
+        let genesis = super::GenesisConfig::<TestConfig> {
+            balances: vec![("Miriam".to_string(), 5)],
+        };
+        assert_eq!(
+            genesis.build().err(),
+            Some("Balance below existential deposit")
+        );
+    }
+
+    // guarda as chamadas que recebeu, na ordem em que aconteceram
+    #[derive(Debug, Clone, Default)]
+    struct RecordHooks {
+        calls: Vec<(String, u32)>,
+    }
+
+    impl super::OnDeposit<String, u32> for RecordHooks {
+        fn on_deposit(&mut self, who: &String, amount: u32) {
+            self.calls.push((who.clone(), amount));
+        }
+    }
+
+    impl super::OnWithdraw<String, u32> for RecordHooks {
+        fn on_withdraw(&mut self, who: &String, amount: u32) {
+            self.calls.push((who.clone(), amount));
+        }
+    }
+
+    impl super::HandleDust<String, u32> for RecordHooks {
+        fn on_dust(&mut self, who: &String, dust: u32) {
+            self.calls.push((who.clone(), dust));
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct HooksConfig;
+
+    impl super::Config for HooksConfig {
+        type AccountId = String;
+        type Amount = u32;
+        const EXISTENTIAL_DEPOSIT: u32 = 10;
+        type MinimumTransfer = ConstU32<5>;
+        const MAX_MEMO_LENGTH: u32 = 8;
+        const ACCRUAL: Option<super::Accrual> = None;
+        type OnDeposit = RecordHooks;
+        type OnWithdraw = RecordHooks;
+        type OnDust = RecordHooks;
+    }
+
+    #[test]
+    fn balance_change_hooks() {
+        let mut balances: super::Pallet<HooksConfig> = super::Pallet::new();
+        let miriam = "Miriam".to_string();
+        let lucio = "Lucio".to_string();
+
+        // o gênesis não chama os ganchos
+        balances.set_balance(&miriam, 100).unwrap();
+
+        // miriam fica só com poeira e é removida
+        assert_eq!(balances.transfer(miriam.clone(), lucio.clone(), 95), Ok(()));
+        assert_eq!(balances.burn(Origin::Root, lucio.clone(), 20), Ok(()));
+
+        // uma transferência que falha não chama nenhum gancho
+        assert_eq!(
+            balances.transfer(miriam.clone(), lucio.clone(), 10),
+            Err("Insufficient balance")
+        );
+
+        // cada gancho é uma instância separada, com as suas próprias chamadas
+        let (deposits, withdrawals, dust) = balances.hooks_mut();
+        assert_eq!(deposits.calls, vec![(lucio.clone(), 95)]);
+        assert_eq!(withdrawals.calls, vec![(miriam.clone(), 95), (lucio, 20)]);
+        assert_eq!(dust.calls, vec![(miriam, 5)]);
+    }
+
+    #[test]
+    fn query_all_balances() {
+        let mut balances: super::Pallet<TestConfig> = super::Pallet::new();
+        let alice = "Alice".to_string();
+        let bob = "Bob".to_string();
+        let charlie = "Charlie".to_string();
+
+        balances.set_balance(&alice, 100).unwrap();
+        balances.set_balance(&bob, 300).unwrap();
+        balances.set_balance(&charlie, 100).unwrap();
+
+        // o saldo reservado conta para os maiores detentores, mas não para `iter_balances`
+        assert_eq!(balances.reserve(&alice, 60), Ok(()));
+
+        assert_eq!(
+            balances.iter_balances().collect::<Vec<_>>(),
+            vec![(&alice, &40), (&bob, &300), (&charlie, &100)]
+        );
+        assert_eq!(balances.account_count(), 3);
+        assert_eq!(balances.top_holders(2), vec![(&bob, 300), (&alice, 100)]);
+        assert_eq!(balances.top_holders(10).len(), 3);
+    }
+
+    #[test]
+    fn frozen_accounts_cannot_transfer() {
+        let mut balances: super::Pallet<TestConfig> = super::Pallet::new();
+        let miriam = "Miriam".to_string();
+        let lucio = "Lucio".to_string();
+
+        balances.set_balance(&miriam, 100).unwrap();
+        balances.set_balance(&lucio, 100).unwrap();
+
+        // só Root pode congelar uma conta
+        assert_eq!(
+            balances.freeze(Origin::Signed(lucio.clone()), miriam.clone()),
+            Err("BadOrigin")
+        );
+        assert_eq!(balances.freeze(Origin::Root, miriam.clone()), Ok(()));
+        assert!(balances.is_frozen(&miriam));
+
+        // a conta congelada não envia nem recebe
+        assert_eq!(
+            balances.transfer(miriam.clone(), lucio.clone(), 10),
+            Err("Account is frozen")
+        );
+        assert_eq!(
+            balances.transfer(lucio.clone(), miriam.clone(), 10),
+            Err("Account is frozen")
+        );
+        assert_eq!(
+            balances.batch_transfer(lucio.clone(), vec![(miriam.clone(), 10)]),
+            Err("Account is frozen")
+        );
+
+        // depois de descongelada, volta a transferir normalmente
+        assert_eq!(balances.thaw(Origin::Root, miriam.clone()), Ok(()));
+        assert_eq!(balances.transfer(miriam.clone(), lucio.clone(), 10), Ok(()));
+        assert_eq!(balances.get_balance(&lucio), 110);
+    }
+
+    #[test]
+    fn minimum_transfer() {
+        let mut balances: super::Pallet<TestConfig> = super::Pallet::new();
+        let miriam = "Miriam".to_string();
+        let lucio = "Lucio".to_string();
+
+        balances.set_balance(&miriam, 100).unwrap();
+        balances.set_balance(&lucio, 100).unwrap();
+
+        // valores abaixo do mínimo são rejeitados, mesmo com saldo suficiente
+        assert_eq!(
+            balances.transfer(miriam.clone(), lucio.clone(), 4),
+            Err("BelowMinimum")
+        );
+        assert_eq!(
+            balances.batch_transfer(
+                miriam.clone(),
+                vec![(lucio.clone(), 10), (lucio.clone(), 4)]
+            ),
+            Err("BelowMinimum")
+        );
+        assert_eq!(balances.transfer(miriam.clone(), lucio.clone(), 5), Ok(()));
+
+        // Root pode transferir abaixo do mínimo em casos especiais
+        assert_eq!(
+            balances.force_transfer(Origin::Root, miriam.clone(), lucio.clone(), 1),
+            Ok(())
+        );
+        assert_eq!(balances.get_balance(&miriam), 94);
+        assert_eq!(balances.get_balance(&lucio), 106);
+    }
+
+    #[test]
+    fn slash_free_then_reserved() {
+        let mut balances: super::Pallet<TestConfig> = super::Pallet::new();
+        let miriam = "Miriam".to_string();
+
+        balances.set_balance(&miriam, 80).unwrap();
+        assert_eq!(balances.reserve(&miriam, 30), Ok(()));
+        balances.set_lock(*b"staking ", &miriam, 50);
+
+        // o saldo livre é cortado primeiro, mesmo travado, e depois o reservado
+        assert_eq!(balances.slash(&miriam, 60), (60, 0));
+        assert_eq!(balances.get_balance(&miriam), 0);
+        assert_eq!(balances.reserved_balance(&miriam), 20);
+        assert_eq!(balances.total_issuance(), 20);
+
+        // cortar mais do que a conta tem retorna o que faltou, e a conta é removida
+        assert_eq!(balances.slash(&miriam, 100), (20, 80));
+        assert!(!balances.account_exists(&miriam));
+        assert_eq!(balances.total_issuance(), 0);
+
+        // uma conta sem saldo não tem o que cortar
+        assert_eq!(balances.slash(&miriam, 10), (0, 10));
+    }
+
+    #[test]
+    fn transfer_with_memo() {
+        let mut balances: super::Pallet<TestConfig> = super::Pallet::new();
+        let miriam = "Miriam".to_string();
+        let lucio = "Lucio".to_string();
+
+        balances.set_balance(&miriam, 100).unwrap();
+
+        // o memo não pode passar do tamanho máximo
+        assert_eq!(
+            balances.transfer_with_memo(miriam.clone(), lucio.clone(), 50, b"pedido-123".to_vec()),
+            Err("Memo too long")
+        );
+
+        assert_eq!(
+            balances.transfer_with_memo(miriam.clone(), lucio.clone(), 50, b"ped-123".to_vec()),
+            Ok(())
+        );
+        assert_eq!(balances.get_balance(&lucio), 50);
+        assert_eq!(
+            balances.take_events(),
+            vec![
+                Event::Transfer {
+                    from: miriam.clone(),
+                    to: lucio.clone(),
+                    amount: 50
+                },
+                Event::Memo {
+                    from: miriam,
+                    to: lucio,
+                    memo: b"ped-123".to_vec()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn transfer_keep_alive() {
+        let mut balances: super::Pallet<TestConfig> = super::Pallet::new();
+        let miriam = "Miriam".to_string();
+        let lucio = "Lucio".to_string();
+        let pedro = "Pedro".to_string();
+
+        balances.set_balance(&miriam, 100).unwrap();
+        balances.set_balance(&pedro, 100).unwrap();
+
+        // com keep alive, a conta não pode ficar abaixo do depósito existencial
+        assert_eq!(
+            balances.transfer_keep_alive(miriam.clone(), lucio.clone(), 95),
+            Err("Transfer would kill account")
+        );
+        assert_eq!(balances.get_balance(&miriam), 100);
+
+        // deixando o depósito existencial, a transferência passa
+        assert_eq!(
+            balances.transfer_keep_alive(miriam.clone(), lucio.clone(), 90),
+            Ok(())
+        );
+        assert_eq!(balances.get_balance(&miriam), 10);
+        assert!(balances.account_exists(&miriam));
+
+        // sem keep alive, a mesma transferência remove a conta
+        assert_eq!(balances.transfer(pedro.clone(), lucio.clone(), 95), Ok(()));
+        assert!(!balances.account_exists(&pedro));
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct InterestConfig;
+
+    impl super::Config for InterestConfig {
+        type AccountId = String;
+        type Amount = u32;
+        const EXISTENTIAL_DEPOSIT: u32 = 10;
+        type MinimumTransfer = ConstU32<5>;
+        const MAX_MEMO_LENGTH: u32 = 8;
+        // 10% por bloco
+        const ACCRUAL: Option<super::Accrual> = Some(super::Accrual::Interest(100_000));
+        type OnDeposit = ();
+        type OnWithdraw = ();
+        type OnDust = ();
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct DemurrageConfig;
+
+    impl super::Config for DemurrageConfig {
+        type AccountId = String;
+        type Amount = u32;
+        const EXISTENTIAL_DEPOSIT: u32 = 10;
+        type MinimumTransfer = ConstU32<5>;
+        const MAX_MEMO_LENGTH: u32 = 8;
+        // 50% por bloco
+        const ACCRUAL: Option<super::Accrual> = Some(super::Accrual::Demurrage(500_000));
+        type OnDeposit = ();
+        type OnWithdraw = ();
+        type OnDust = ();
+    }
+
+    #[test]
+    fn accrual_on_finalize() {
+        use crate::support::Hooks;
+
+        // juros criam moedas novas ao final de cada bloco
+        let mut balances: super::Pallet<InterestConfig> = super::Pallet::new();
+        balances.set_balance(&"Miriam".to_string(), 1000).unwrap();
+        balances.set_balance(&"Lucio".to_string(), 10).unwrap();
+        balances.on_finalize(1u32);
+        assert_eq!(balances.get_balance(&"Miriam".to_string()), 1100);
+        assert_eq!(balances.get_balance(&"Lucio".to_string()), 11);
+        assert_eq!(balances.total_issuance(), 1111);
+        assert!(balances.take_events().contains(&Event::Minted {
+            who: "Miriam".to_string(),
+            amount: 100
+        }));
+
+        // demurrage destrói parte dos saldos, e quem fica com poeira é removido
+        let mut balances: super::Pallet<DemurrageConfig> = super::Pallet::new();
+        balances.set_balance(&"Miriam".to_string(), 1000).unwrap();
+        balances.set_balance(&"Lucio".to_string(), 15).unwrap();
+        balances.on_finalize(1u32);
+        assert_eq!(balances.get_balance(&"Miriam".to_string()), 500);
+        assert!(!balances.account_exists(&"Lucio".to_string()));
+        assert_eq!(balances.total_issuance(), 500);
+
+        // cada variação fica registrada nos eventos
+        assert_eq!(
+            balances.take_events(),
+            vec![
+                Event::Burned {
+                    who: "Lucio".to_string(),
+                    amount: 7
+                },
+                Event::Reaped {
+                    who: "Lucio".to_string(),
+                    dust: 8
+                },
+                Event::Burned {
+                    who: "Miriam".to_string(),
+                    amount: 500
+                },
+            ]
+        );
+
+        // a parte travada por locks ou em hold não sofre demurrage
+        let pedro = "Pedro".to_string();
+        balances.set_balance(&pedro, 1000).unwrap();
+        balances.set_lock(*b"staking ", &pedro, 600);
+        assert_eq!(balances.hold(*b"poe/clm ", &pedro, 200), Ok(()));
+        balances.on_finalize(2u32);
+        assert_eq!(balances.get_balance(&pedro), 800);
+        balances.on_finalize(3u32);
+        assert_eq!(balances.get_balance(&pedro), 700);
+        assert_eq!(balances.locked_balance(&pedro), 600);
+        assert_eq!(balances.total_balance_on_hold(&pedro), 200);
+
+        // sem taxa configurada, nada muda
+        let mut balances: super::Pallet<TestConfig> = super::Pallet::new();
+        balances.set_balance(&"Miriam".to_string(), 1000).unwrap();
+        balances.on_finalize(1u32);
+        assert_eq!(balances.get_balance(&"Miriam".to_string()), 1000);
+    }
+
+    #[test]
+    fn try_state_checks_total_issuance() {
+        use crate::support::Hooks;
+
+        let mut balances: super::Pallet<TestConfig> = super::Pallet::new();
+        balances.set_balance(&"Miriam".to_string(), 100).unwrap();
+        balances.set_balance(&"Lucio".to_string(), 50).unwrap();
+        assert_eq!(balances.reserve(&"Lucio".to_string(), 20), Ok(()));
+        assert_eq!(Hooks::<u32>::try_state(&balances), Ok(()));
+
+        // um saldo escrito sem ajustar o total quebra o invariante
+        balances.write_balance(&"Miriam".to_string(), 90);
+        assert_eq!(
+            Hooks::<u32>::try_state(&balances),
+            Err("Total issuance does not match the sum of all balances")
+        );
+    }
+
+    #[test]
+    fn holds_add_up_and_block_transfers() {
+        let mut balances: super::Pallet<TestConfig> = super::Pallet::new();
+        let miriam = "Miriam".to_string();
+        let lucio = "Lucio".to_string();
+
+        balances.set_balance(&miriam, 100).unwrap();
+
+        // holds de motivos diferentes se somam
+        assert_eq!(balances.hold(*b"poe/clm ", &miriam, 30), Ok(()));
+        assert_eq!(balances.hold(*b"staking ", &miriam, 40), Ok(()));
+        assert_eq!(balances.total_balance_on_hold(&miriam), 70);
+        assert_eq!(
+            balances.hold(*b"staking ", &miriam, 31),
+            Err("Insufficient balance")
+        );
+
+        // o saldo em hold continua livre, mas não pode ser transferido
+        assert_eq!(balances.get_balance(&miriam), 100);
+        assert_eq!(balances.usable_balance(&miriam), 30);
+        assert_eq!(
+            balances.transfer(miriam.clone(), lucio.clone(), 40),
+            Err("Balance is on hold")
+        );
+        assert_eq!(balances.transfer(miriam.clone(), lucio.clone(), 30), Ok(()));
+
+        // liberar um motivo não mexe no outro
+        assert_eq!(balances.release(*b"poe/clm ", &miriam, 50), 20);
+        assert_eq!(balances.balance_on_hold(*b"poe/clm ", &miriam), 0);
+        assert_eq!(balances.balance_on_hold(*b"staking ", &miriam), 40);
+        assert_eq!(balances.transfer(miriam.clone(), lucio.clone(), 30), Ok(()));
+        assert_eq!(balances.get_balance(&miriam), 40);
+    }
+
+    #[test]
+    fn holds_never_exceed_free_balance() {
+        use crate::support::Hooks;
+
+        let mut balances: super::Pallet<TestConfig> = super::Pallet::new();
+        let miriam = "Miriam".to_string();
+        balances.set_balance(&miriam, 100).unwrap();
+        balances.take_events();
+
+        // um hold de zero não faz nada nem emite evento
+        assert_eq!(balances.hold(*b"staking ", &miriam, 0), Ok(()));
+        assert!(balances.take_events().is_empty());
+        assert!(balances.holds.get(&miriam).is_none());
+
+        // um corte leva junto a parte dos holds que não cabe mais no saldo livre,
+        // começando pelo primeiro motivo
+        balances.hold(*b"poe/clm ", &miriam, 30).unwrap();
+        balances.hold(*b"staking ", &miriam, 40).unwrap();
+        assert_eq!(balances.slash(&miriam, 50), (50, 0));
+        assert_eq!(balances.get_balance(&miriam), 50);
+        assert_eq!(balances.balance_on_hold(*b"poe/clm ", &miriam), 10);
+        assert_eq!(balances.balance_on_hold(*b"staking ", &miriam), 40);
+        assert_eq!(Hooks::<u32>::try_state(&balances), Ok(()));
+
+        // o mesmo vale para o saldo definido pelo `Root`
+        balances
+            .force_set_balance(Origin::Root, miriam.clone(), 20)
+            .unwrap();
+        assert_eq!(balances.balance_on_hold(*b"poe/clm ", &miriam), 0);
+        assert_eq!(balances.balance_on_hold(*b"staking ", &miriam), 20);
+        assert_eq!(balances.total_balance_on_hold(&miriam), 20);
+        assert_eq!(Hooks::<u32>::try_state(&balances), Ok(()));
+
+        // um saldo escrito sem respeitar os holds quebra o invariante
+        balances.write_balance(&miriam, 10);
+        balances.total_issuance = 10;
+        assert_eq!(
+            Hooks::<u32>::try_state(&balances),
+            Err("Balance on hold exceeds free balance")
+        );
+    }
+
+    #[test]
+    fn pallet_account_holds_balance() {
+        let mut balances: super::Pallet<TestConfig> = super::Pallet::new();
+        let treasury: String = PalletId(*b"py/trsry").into_account();
+        let miriam = "Miriam".to_string();
+        balances.set_balance(&miriam, 100).unwrap();
+
+        // a conta derivada do pallet recebe e envia fundos como qualquer outra
+        assert_eq!(
+            balances.transfer(miriam.clone(), treasury.clone(), 60),
+            Ok(())
+        );
+        assert!(balances.account_exists(&treasury));
+        assert_eq!(balances.get_balance(&treasury), 60);
+        assert_eq!(
+            balances.force_transfer(Origin::Root, treasury.clone(), miriam.clone(), 20),
+            Ok(())
+        );
+        assert_eq!(balances.get_balance(&treasury), 40);
+
+        // e a conta é a mesma sempre que derivada do mesmo identificador
+        assert_eq!(
+            balances.get_balance(&PalletId(*b"py/trsry").into_account()),
+            40
+        );
+        assert_eq!(balances.total_issuance(), 100);
+    }
 }
@@ -1,10 +1,11 @@
-use support::Dispatch;
+use support::{Dispatch, Hasher};
 
 // importando os módulos
 mod balances;
 mod proof_of_existence;
 mod support;
 mod system;
+mod transaction_payment;
 
 // configuramos tipos para serem passados como argumento para os Pallets
 mod types {
@@ -16,13 +17,22 @@ mod types {
     pub type BlockNumber = u32;
     pub type Nonce = u32;
 
+    // como os valores são exibidos: 100 na menor unidade é 1 UNIT
+    pub const TOKEN: support::TokenFormat = support::TokenFormat {
+        decimals: 2,
+        symbol: "UNIT",
+    };
+
     // tipos específicos para execução de blocos
-    pub type Extrinsic = support::Extrinsic<AccountId, crate::RuntimeCall>;
+    pub type Extrinsic = support::Extrinsic<AccountId, crate::RuntimeCall, Amount>;
     pub type Header = support::Header<BlockNumber>;
     pub type Block = support::Block<Header, Extrinsic>;
 
-    // tipos para Proof Of Existence
-    pub type Content = String;
+    // tipos para Proof Of Existence: os claims guardam o hash do conteúdo
+    pub type Content = support::H256;
+
+    // o tesouro da chain, que recebe as taxas. A conta dele é derivada do identificador
+    pub const TREASURY: support::PalletId = support::PalletId(*b"py/trsry");
 }
 
 // implento o a trait config do system.rs para Runtime
@@ -31,6 +41,9 @@ impl system::Config for Runtime {
     type AccountId = types::AccountId;
     type BlockNumber = types::BlockNumber;
     type Nonce = types::Nonce;
+    type Amount = types::Amount;
+    const MAX_EXTRINSICS_PER_BLOCK: u32 = 100;
+    type RuntimeEvent = RuntimeEvent;
 }
 
 // implento o a trait config do balances.rs para Runtime
@@ -38,10 +51,83 @@ impl system::Config for Runtime {
 impl balances::Config for Runtime {
     type AccountId = types::AccountId;
     type Amount = types::Amount;
+    const EXISTENTIAL_DEPOSIT: types::Amount = 1;
+    type MinimumTransfer = support::ConstU32<1>;
+    const MAX_MEMO_LENGTH: u32 = 256;
+    const ACCRUAL: Option<balances::Accrual> = None;
+    type OnDeposit = ();
+    type OnWithdraw = ();
+    type OnDust = ();
 }
 
 impl proof_of_existence::Config for Runtime {
     type Content = types::Content;
+    type Hasher = support::Sha256;
+    const CLAIM_LIFETIME: Option<types::BlockNumber> = None;
+    type Currency = balances::Pallet<Runtime>;
+    type ClaimDeposit = support::ConstU32<50>;
+}
+
+impl transaction_payment::Config for Runtime {
+    type AccountId = types::AccountId;
+    type Amount = types::Amount;
+    type Currency = balances::Pallet<Runtime>;
+}
+
+/// Estado inicial (gênesis) da blockchain
+pub struct GenesisConfig {
+    pub balances: balances::GenesisConfig<Runtime>,
+    pub transaction_payment: transaction_payment::GenesisConfig<Runtime>,
+}
+
+impl Runtime {
+    /// Cria o runtime a partir do estado inicial definido em `config`,
+    /// em vez de ajustar os saldos manualmente depois do `Runtime::new`
+    fn from_genesis(config: GenesisConfig) -> Result<Self, &'static str> {
+        let mut runtime = Self::new();
+        runtime.balances = config.balances.build()?;
+        runtime.transaction_payment = config.transaction_payment.build();
+        Ok(runtime)
+    }
+}
+
+// cada chamada declara a taxa base cobrada de quem a envia
+impl RuntimeCall {
+    pub fn base_fee(&self) -> types::Amount {
+        match self {
+            RuntimeCall::balances(_) => 1,
+            RuntimeCall::transaction_payment(_) => 1,
+            RuntimeCall::proof_of_existence(_) => 5,
+        }
+    }
+}
+
+// antes de cada extrinsic, o runtime cobra a taxa base da chamada (mais a gorjeta)
+// de quem a assinou. As gorjetas pagas ficam registradas por bloco no system
+impl support::ChargeTransaction for Runtime {
+    type Caller = types::AccountId;
+    type Call = RuntimeCall;
+    type Tip = types::Amount;
+
+    fn charge_transaction(
+        &mut self,
+        caller: &Self::Caller,
+        call: &Self::Call,
+        tip: Option<Self::Tip>,
+    ) -> support::DispatchResult {
+        let tip = tip.unwrap_or(0);
+        self.transaction_payment
+            .charge_fee(&mut self.balances, caller, call.base_fee(), tip)?;
+        self.system.note_tip(tip);
+        Ok(())
+    }
+}
+
+// os depósitos dos claims pedidos pelo proof_of_existence são reservados e devolvidos no balances
+impl support::Settle for Runtime {
+    fn settle(&mut self) -> support::DispatchResult {
+        self.proof_of_existence.settle_deposits(&mut self.balances)
+    }
 }
 
 /// Estrutura principal que representa o runtime da blockchain.
@@ -70,6 +156,9 @@ pub struct Runtime {
     /// Módulo responsável por gerenciar os saldos das contas
     balances: balances::Pallet<Runtime>,
 
+    /// Módulo que cobra as taxas das extrinsics
+    transaction_payment: transaction_payment::Pallet<Runtime>,
+
     /// Módulo que implementa a funcionalidade de prova de existência
     proof_of_existence: proof_of_existence::Pallet<Runtime>
 }
@@ -78,17 +167,26 @@ pub struct Runtime {
 fn main() {
     // simulando ações na blockchain
 
-    // instanciamos o runtime.
-    // esse é genesis state.
-    // cada blockchain inicia dessa forma: sem transações
-    let mut runtime = Runtime::new();
-
     // nossos usuários
     let miriam: String = "miriam".to_string();
     let lucio: String = "lucio".to_string();
 
-    // definimos os saldos para miriam no valor de 10.000
-    runtime.balances.set_balance(&miriam, 10000);
+    // os valores são escritos como os usuários os veem, e convertidos para a menor unidade
+    let amount = |text: &str| types::TOKEN.parse(text).expect("Invalid amount");
+
+    // instanciamos o runtime a partir do genesis state.
+    // cada blockchain inicia dessa forma: sem transações,
+    // apenas com os saldos iniciais (miriam com 100 UNIT, ou 10.000 na menor unidade)
+    let mut runtime = Runtime::from_genesis(GenesisConfig {
+        balances: balances::GenesisConfig {
+            balances: vec![(miriam.clone(), amount("100 UNIT"))],
+        },
+        // as taxas vão para o tesouro, em vez de serem queimadas
+        transaction_payment: transaction_payment::GenesisConfig {
+            fee_destination: Some(types::TREASURY.into_account()),
+        },
+    })
+    .expect("Invalid genesis config");
 
     // preparando o bloco 1
     let block_1 = types::Block {
@@ -99,13 +197,15 @@ fn main() {
             caller: miriam.clone(),
             call: RuntimeCall::balances(balances::Call::transfer {
                 to: lucio.clone(),
-                amount: 100,
+                amount: amount("1 UNIT"),
             }),
+            // miriam paga uma gorjeta além da taxa base
+            tip: Some(amount("0.02")),
         }],
     };
 
     // executamos a transação
-    let _ = runtime
+    runtime
         .execute_block(block_1)
         .expect("Failed to execute block 1");
 
@@ -115,13 +215,14 @@ fn main() {
         extrinsic: vec![support::Extrinsic {
             caller: lucio.clone(),
             call: RuntimeCall::proof_of_existence(proof_of_existence::Call::create_claim {
-                claim: "MY_DOC".to_string(),
+                claim: support::Sha256::hash(b"MY_DOC"),
             }),
+            tip: None,
         }],
     };
 
     // executamos a transação
-    let _ = runtime
+    runtime
         .execute_block(block_2)
         .expect("Failed to execute block 2");
 
@@ -131,32 +232,517 @@ fn main() {
         extrinsic: vec![support::Extrinsic {
             caller: lucio.clone(),
             call: RuntimeCall::proof_of_existence(proof_of_existence::Call::revoke_claim {
-                claim: "MY_DOC".to_string(),
+                claim: support::Sha256::hash(b"MY_DOC"),
             }),
+            tip: None,
         }],
     };
 
     // executamos a transação
-    let _ = runtime
+    runtime
         .execute_block(block_3)
         .expect("Failed to execute block 3");
 
-    // preparando o bloco 4 para criação de um `claim`
+    // preparando o bloco 4 para criação de um `claim` de um arquivo que já está no IPFS.
+    // o claim é feito a partir do CID do arquivo, e é o mesmo hash que o `Sha256` calcularia
+    // a partir dos bytes dele (`documento_da_miriam`)
+    let document =
+        support::Cid::parse("bafkreicc37eahqtifk24babxtxaahurjcpmxprma44qc3ek7xluqqt7m2y")
+            .expect("Invalid CID");
     let block_4 = types::Block {
         header: support::Header { block_number: 4 },
         extrinsic: vec![support::Extrinsic {
             caller: miriam.clone(),
             call: RuntimeCall::proof_of_existence(proof_of_existence::Call::create_claim {
-                claim: "documento_da_miriam".to_string(),
+                claim: document.into(),
             }),
+            tip: None,
         }],
     };
 
     // executamos a transação
-    let _ = runtime
+    runtime
         .execute_block(block_4)
         .expect("Failed to execute block 3");
 
     // exibo que há dentro do runtime
-    println!("{:#?}", runtime)
+    println!("{:#?}", runtime);
+
+    // os valores são exibidos formatados.
+    // com um formato inválido, mostramos o valor na menor unidade
+    let show = |amount: types::Amount| {
+        types::TOKEN
+            .format(amount)
+            .unwrap_or_else(|_| amount.to_string())
+    };
+
+    // os saldos finais
+    for (account, balance) in runtime.balances.iter_balances() {
+        println!("{}: {}", account, show(*balance));
+    }
+
+    // as taxas e gorjetas cobradas
+    println!(
+        "taxas: {} (destino: {:?})",
+        show(runtime.transaction_payment.total_fees()),
+        runtime.transaction_payment.fee_destination()
+    );
+    println!(
+        "gorjetas: {} no bloco 1, {} no último bloco",
+        show(runtime.system.tips_at(&1)),
+        show(runtime.system.tips())
+    );
+
+    // o que cada usuário fez: as extrinsics enviadas e os eventos que o citam
+    let last_block = runtime.system.block_number();
+    println!("eventos no último bloco: {}", runtime.system.events().len());
+    for account in [&miriam, &lucio] {
+        println!(
+            "{}: {} extrinsics, citado em {} eventos",
+            account,
+            runtime.system.get_nonce(account),
+            runtime
+                .system
+                .events_for_account(account, 1..=last_block)
+                .len()
+        );
+    }
+
+    // e quem atestou o documento da miriam, além dela
+    println!(
+        "atestações do documento da miriam: {:?}",
+        runtime
+            .proof_of_existence
+            .get_attestations(&document.into())
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // runtime com a miriam começando com 10.000
+    fn new_runtime() -> Runtime {
+        Runtime::from_genesis(GenesisConfig {
+            balances: balances::GenesisConfig {
+                balances: vec![("miriam".to_string(), 10000)],
+            },
+            transaction_payment: transaction_payment::GenesisConfig {
+                fee_destination: Some(types::TREASURY.into_account()),
+            },
+        })
+        .unwrap()
+    }
+
+    // bloco com `count` transferências de 1 da miriam para o lucio
+    fn transfer_block(block_number: types::BlockNumber, count: u32) -> types::Block {
+        types::Block {
+            header: support::Header { block_number },
+            extrinsic: (0..count)
+                .map(|_| support::Extrinsic {
+                    caller: "miriam".to_string(),
+                    call: RuntimeCall::balances(balances::Call::transfer {
+                        to: "lucio".to_string(),
+                        amount: 1,
+                    }),
+                    tip: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn fees_go_to_treasury() {
+        let mut runtime = new_runtime();
+        let treasury: types::AccountId = types::TREASURY.into_account();
+        assert_eq!(
+            runtime.transaction_payment.fee_destination(),
+            Some(&treasury)
+        );
+
+        // a taxa base de cada transferência vai para a conta do tesouro, sem queimar nada
+        assert_eq!(runtime.execute_block(transfer_block(1, 3)), Ok(()));
+        assert_eq!(runtime.balances.get_balance(&treasury), 3);
+        assert_eq!(runtime.transaction_payment.total_fees(), 3);
+        assert_eq!(runtime.balances.total_issuance(), 10000);
+    }
+
+    #[test]
+    fn max_extrinsics_per_block() {
+        let mut runtime = new_runtime();
+        let max = <Runtime as system::Config>::MAX_EXTRINSICS_PER_BLOCK;
+        let lucio = "lucio".to_string();
+
+        // um bloco cheio é aceito
+        assert_eq!(runtime.execute_block(transfer_block(1, max)), Ok(()));
+        assert_eq!(runtime.balances.get_balance(&lucio), max as types::Amount);
+
+        // com uma extrinsic a mais, o bloco é rejeitado sem executar nada
+        assert_eq!(
+            runtime.execute_block(transfer_block(2, max + 1)),
+            Err("block has too many extrinsics")
+        );
+        assert_eq!(runtime.system.block_number(), 1);
+        assert_eq!(runtime.balances.get_balance(&lucio), max as types::Amount);
+    }
+
+    #[test]
+    fn extrinsic_rejected_when_fee_cannot_be_paid() {
+        let mut runtime = new_runtime();
+        let ana = "ana".to_string();
+
+        // a ana não tem saldo para pagar a taxa, então a transferência nem é executada
+        let block = types::Block {
+            header: support::Header { block_number: 1 },
+            extrinsic: vec![support::Extrinsic {
+                caller: ana.clone(),
+                call: RuntimeCall::balances(balances::Call::transfer {
+                    to: "lucio".to_string(),
+                    amount: 0,
+                }),
+                tip: None,
+            }],
+        };
+        assert_eq!(runtime.execute_block(block), Ok(()));
+
+        let events = runtime.system.events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].phase, system::Phase::ApplyExtrinsic(0));
+        assert!(matches!(
+            &events[0].event,
+            RuntimeEvent::system(system::Event::ExtrinsicFailed { error })
+                if *error == "Cannot pay transaction fee".into()
+        ));
+        assert_eq!(runtime.system.get_nonce(&ana), 0);
+        assert_eq!(runtime.transaction_payment.total_fees(), 0);
+        assert_eq!(runtime.balances.total_issuance(), 10000);
+    }
+
+    #[test]
+    fn events_for_account() {
+        let mut runtime = new_runtime();
+        let (miriam, lucio) = ("miriam".to_string(), "lucio".to_string());
+
+        // bloco 1: transferência para o lucio. bloco 2: claim da miriam. bloco 3: vazio
+        assert_eq!(runtime.execute_block(transfer_block(1, 1)), Ok(()));
+        let claim_block = types::Block {
+            header: support::Header { block_number: 2 },
+            extrinsic: vec![support::Extrinsic {
+                caller: miriam.clone(),
+                call: RuntimeCall::proof_of_existence(proof_of_existence::Call::create_claim {
+                    claim: support::Sha256::hash(b"MY_DOC"),
+                }),
+                tip: None,
+            }],
+        };
+        assert_eq!(runtime.execute_block(claim_block), Ok(()));
+        assert_eq!(runtime.execute_block(transfer_block(3, 0)), Ok(()));
+
+        // o lucio só aparece na transferência do bloco 1
+        let events = runtime.system.events_for_account(&lucio, 1..=3);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].0, 1);
+        assert!(matches!(
+            events[0].1.event,
+            RuntimeEvent::balances(balances::Event::Transfer { .. })
+        ));
+
+        // no bloco 2, a miriam pagou a taxa (queimada), reservou o depósito e criou o claim
+        let events = runtime.system.events_for_account(&miriam, 2..=2);
+        assert_eq!(events.len(), 4);
+        assert!(matches!(
+            events[2].1.event,
+            RuntimeEvent::balances(balances::Event::Reserved { amount: 50, .. })
+        ));
+        assert!(matches!(
+            events[3].1.event,
+            RuntimeEvent::proof_of_existence(proof_of_existence::Event::ClaimCreated { .. })
+        ));
+
+        assert!(runtime
+            .system
+            .events_for_account(&"ana".to_string(), 1..=3)
+            .is_empty());
+    }
+
+    #[test]
+    fn claim_deposits() {
+        let mut runtime = new_runtime();
+        let (miriam, lucio) = ("miriam".to_string(), "lucio".to_string());
+        let claim = support::Sha256::hash(b"MY_DOC");
+        let claim_call = |call| support::Extrinsic {
+            caller: miriam.clone(),
+            call: RuntimeCall::proof_of_existence(call),
+            tip: None,
+        };
+
+        // criar o claim reserva o depósito da miriam, além da taxa
+        let block = types::Block {
+            header: support::Header { block_number: 1 },
+            extrinsic: vec![claim_call(proof_of_existence::Call::create_claim { claim })],
+        };
+        assert_eq!(runtime.execute_block(block), Ok(()));
+        assert_eq!(runtime.balances.reserved_balance(&miriam), 50);
+        assert_eq!(runtime.balances.get_balance(&miriam), 10000 - 5 - 50);
+
+        // revogar o claim devolve o depósito
+        let block = types::Block {
+            header: support::Header { block_number: 2 },
+            extrinsic: vec![claim_call(proof_of_existence::Call::revoke_claim { claim })],
+        };
+        assert_eq!(runtime.execute_block(block), Ok(()));
+        assert_eq!(runtime.balances.reserved_balance(&miriam), 0);
+        assert_eq!(runtime.balances.get_balance(&miriam), 10000 - 5 - 5);
+
+        // o lucio paga a taxa, mas não tem saldo para o depósito: o claim não é criado
+        assert_eq!(runtime.balances.set_balance(&lucio, 20), Ok(()));
+        let block = types::Block {
+            header: support::Header { block_number: 3 },
+            extrinsic: vec![support::Extrinsic {
+                caller: lucio.clone(),
+                call: RuntimeCall::proof_of_existence(proof_of_existence::Call::create_claim {
+                    claim,
+                }),
+                tip: None,
+            }],
+        };
+        assert_eq!(runtime.execute_block(block), Ok(()));
+        assert_eq!(runtime.proof_of_existence.get_claim(&claim), None);
+        assert_eq!(runtime.balances.get_balance(&lucio), 15);
+        assert_eq!(runtime.balances.reserved_balance(&lucio), 0);
+        assert!(runtime.system.events().iter().any(|record| matches!(
+            &record.event,
+            RuntimeEvent::system(system::Event::ExtrinsicFailed { error })
+                if error.error == "Cannot reserve claim deposit"
+        )));
+    }
+
+    #[test]
+    fn runtime_metadata() {
+        let metadata = RuntimeCall::metadata();
+        let names: Vec<_> = metadata.iter().map(|pallet| pallet.name).collect();
+        assert_eq!(
+            names,
+            vec!["balances", "transaction_payment", "proof_of_existence"]
+        );
+
+        // cada pallet descreve as suas chamadas e os seus eventos
+        let balances = &metadata[0];
+        assert!(balances.calls.iter().any(|call| call.name == "transfer"));
+        let minted = balances
+            .events
+            .iter()
+            .find(|event| event.name == "Minted")
+            .unwrap();
+        assert_eq!(
+            minted.docs,
+            &["`amount` novas moedas foram criadas na conta `who`"]
+        );
+
+        // e os seus erros, pela mensagem que as chamadas retornam
+        let frozen = balances
+            .errors
+            .iter()
+            .find(|error| error.message == "Account is frozen")
+            .unwrap();
+        assert_eq!(
+            frozen.docs,
+            &["A conta está congelada e não pode enviar nem receber transferências"]
+        );
+    }
+
+    // um runtime só para os testes, com um pallet cuja chamada muda o próprio estado e entra em
+    // pânico. Nenhum pallet de verdade tem uma chamada assim, então ele fica só aqui
+    mod panicking_dispatch {
+        use crate::support::Dispatch;
+        use crate::{balances, support, system, transaction_payment};
+
+        mod types {
+            use crate::support;
+
+            pub type BlockNumber = u32;
+            pub type Extrinsic = support::Extrinsic<String, super::RuntimeCall, u128>;
+            pub type Header = support::Header<BlockNumber>;
+            pub type Block = support::Block<Header, Extrinsic>;
+        }
+
+        pub mod faulty {
+            use crate::support::{DispatchResult, EventTopics, Hooks, JournaledMap, Transactional};
+
+            pub trait Config: crate::system::Config {}
+
+            #[macros::event]
+            #[derive(Debug, Clone, PartialEq)]
+            pub enum Event<T: Config> {
+                /// O contador de `who` aumentou
+                Counted { who: T::AccountId },
+            }
+
+            impl<T: Config> EventTopics<T::AccountId> for Event<T> {}
+
+            // a chamada entra em pânico em vez de retornar um erro
+            #[macros::error]
+            pub mod errors {}
+
+            #[derive(Debug)]
+            pub struct Pallet<T: Config> {
+                pub counts: JournaledMap<T::AccountId, u32>,
+                events: Vec<Event<T>>,
+            }
+
+            #[macros::call]
+            impl<T: Config> Pallet<T> {
+                /// Aumenta o contador de `who` e entra em pânico
+                pub fn boom(&mut self, _caller: T::AccountId, who: T::AccountId) -> DispatchResult {
+                    *self.counts.get_or_default(who.clone()) += 1;
+                    self.events.push(Event::Counted { who });
+                    panic!("boom");
+                }
+            }
+
+            impl<T: Config> Pallet<T> {
+                pub fn new() -> Self {
+                    Self {
+                        counts: JournaledMap::new(),
+                        events: Vec::new(),
+                    }
+                }
+
+                pub fn take_events(&mut self) -> Vec<Event<T>> {
+                    std::mem::take(&mut self.events)
+                }
+            }
+
+            impl<T: Config, BlockNumber> Hooks<BlockNumber> for Pallet<T> {}
+
+            impl<T: Config> Transactional for Pallet<T> {
+                fn begin(&mut self) {
+                    self.counts.begin();
+                }
+
+                fn commit(&mut self) {
+                    self.counts.commit();
+                }
+
+                fn rollback(&mut self) {
+                    self.counts.rollback();
+                }
+            }
+        }
+
+        #[derive(Debug)]
+        #[macros::runtime]
+        pub struct Runtime {
+            system: system::Pallet<Runtime>,
+            balances: balances::Pallet<Runtime>,
+            transaction_payment: transaction_payment::Pallet<Runtime>,
+            faulty: faulty::Pallet<Runtime>,
+        }
+
+        impl system::Config for Runtime {
+            type AccountId = String;
+            type BlockNumber = u32;
+            type Nonce = u32;
+            type Amount = u128;
+            const MAX_EXTRINSICS_PER_BLOCK: u32 = 10;
+            type RuntimeEvent = RuntimeEvent;
+        }
+
+        impl balances::Config for Runtime {
+            type AccountId = String;
+            type Amount = u128;
+            const EXISTENTIAL_DEPOSIT: u128 = 1;
+            type MinimumTransfer = support::ConstU32<1>;
+            const MAX_MEMO_LENGTH: u32 = 8;
+            const ACCRUAL: Option<balances::Accrual> = None;
+            type OnDeposit = ();
+            type OnWithdraw = ();
+            type OnDust = ();
+        }
+
+        impl transaction_payment::Config for Runtime {
+            type AccountId = String;
+            type Amount = u128;
+            type Currency = balances::Pallet<Runtime>;
+        }
+
+        impl faulty::Config for Runtime {}
+
+        impl support::Settle for Runtime {
+            fn settle(&mut self) -> support::DispatchResult {
+                Ok(())
+            }
+        }
+
+        // toda extrinsic paga uma taxa de 5
+        impl support::ChargeTransaction for Runtime {
+            type Caller = String;
+            type Call = RuntimeCall;
+            type Tip = u128;
+
+            fn charge_transaction(
+                &mut self,
+                caller: &String,
+                _call: &RuntimeCall,
+                tip: Option<u128>,
+            ) -> support::DispatchResult {
+                self.transaction_payment
+                    .charge_fee(&mut self.balances, caller, 5, tip.unwrap_or(0))
+            }
+        }
+
+        #[test]
+        fn panicking_extrinsic_is_rolled_back() {
+            let mut runtime = Runtime::new();
+            let (miriam, lucio) = ("miriam".to_string(), "lucio".to_string());
+            runtime.balances.set_balance(&miriam, 100).unwrap();
+
+            let block = types::Block {
+                header: support::Header { block_number: 1 },
+                extrinsic: vec![
+                    support::Extrinsic {
+                        caller: miriam.clone(),
+                        call: RuntimeCall::faulty(faulty::Call::boom { who: lucio.clone() }),
+                        tip: None,
+                    },
+                    support::Extrinsic {
+                        caller: miriam.clone(),
+                        call: RuntimeCall::balances(balances::Call::transfer {
+                            to: lucio.clone(),
+                            amount: 10,
+                        }),
+                        tip: None,
+                    },
+                ],
+            };
+            assert_eq!(runtime.execute_block(block), Ok(()));
+
+            // o que a chamada mudou antes do pânico foi desfeito, mas a taxa foi cobrada
+            assert!(runtime.faulty.counts.is_empty());
+            assert_eq!(runtime.transaction_payment.total_fees(), 10);
+            assert_eq!(runtime.balances.get_balance(&miriam), 80);
+            assert_eq!(runtime.balances.get_balance(&lucio), 10);
+
+            let expected = support::DispatchError {
+                pallet: Some("faulty"),
+                call: Some("boom"),
+                error: "Extrinsic panicked",
+            };
+            assert!(runtime.system.events().iter().any(|record| {
+                record.phase == system::Phase::ApplyExtrinsic(0)
+                    && matches!(
+                        &record.event,
+                        RuntimeEvent::system(system::Event::ExtrinsicFailed { error })
+                            if *error == expected
+                    )
+            }));
+
+            // o evento emitido antes do pânico também foi descartado
+            assert!(!runtime
+                .system
+                .events()
+                .iter()
+                .any(|record| matches!(record.event, RuntimeEvent::faulty(_))));
+        }
+    }
 }
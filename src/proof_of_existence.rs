@@ -1,9 +1,138 @@
-use crate::support::DispatchResult;
+use crate::support::{
+    Currency, DispatchResult, EventTopics, Get, Hasher, Hooks, JournaledMap, Transactional,
+};
 use core::fmt::Debug;
-use std::collections::BTreeMap;
+use num::traits::{CheckedAdd, Zero};
+use std::collections::BTreeSet;
 
 pub trait Config: crate::system::Config {
-    type Content: Debug + Ord;
+    /// O que fica guardado em cada claim. No runtime, é o hash do conteúdo,
+    /// que também pode vir do CID de um arquivo no IPFS (veja `support::Cid`)
+    type Content: Debug + Ord + Clone;
+
+    /// Função de hash que transforma o conteúdo dos usuários em `Content`
+    type Hasher: Hasher<Output = Self::Content>;
+
+    /// Por quantos blocos um claim vale. Depois disso ele expira, é removido no fim do bloco
+    /// e qualquer um pode criá-lo de novo. Com `None`, os claims nunca expiram
+    const CLAIM_LIFETIME: Option<Self::BlockNumber>;
+
+    /// A moeda em que os depósitos dos claims ficam reservados
+    type Currency: Currency<Self::AccountId, Self::Amount>;
+
+    /// Quanto o dono de um claim deixa reservado enquanto o claim existir.
+    /// O depósito é devolvido quando o claim é revogado ou expira
+    type ClaimDeposit: Get<Self::Amount>;
+}
+
+// mudança no saldo reservado de uma conta, pedida pelo pallet e aplicada pelo runtime
+// no `Currency` através de `settle_deposits`
+#[derive(Debug, Clone, PartialEq)]
+enum DepositChange<AccountId, Amount> {
+    Reserve(AccountId, Amount),
+    Release(AccountId, Amount),
+}
+
+/// Informações guardadas sobre cada claim
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClaimDetails<AccountId, BlockNumber, Amount, Content> {
+    /// Dono do claim
+    pub owner: AccountId,
+
+    /// Bloco em que o claim foi criado
+    pub created_at: BlockNumber,
+
+    /// Valor reservado do dono enquanto o claim existir
+    pub deposit: Amount,
+
+    /// Contas que atestaram o claim, além do dono
+    pub attesters: BTreeSet<AccountId>,
+
+    /// Versão do documento: começa em 1 e aumenta a cada `update_claim`
+    pub version: u32,
+
+    /// O conteúdo da versão anterior do documento, se houver
+    pub previous: Option<Content>,
+}
+
+/// `ClaimDetails` com os tipos do runtime
+pub type ClaimDetailsOf<T> = ClaimDetails<
+    <T as crate::system::Config>::AccountId,
+    <T as crate::system::Config>::BlockNumber,
+    <T as crate::system::Config>::Amount,
+    <T as Config>::Content,
+>;
+
+/// Eventos emitidos pelo módulo de Prova de Existência
+#[macros::event]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event<T: Config> {
+    /// `owner` criou o `claim`
+    ClaimCreated {
+        owner: T::AccountId,
+        claim: T::Content,
+    },
+
+    /// `owner` revogou o `claim`
+    ClaimRevoked {
+        owner: T::AccountId,
+        claim: T::Content,
+    },
+
+    /// O `claim` de `owner` expirou e foi removido
+    ClaimExpired {
+        owner: T::AccountId,
+        claim: T::Content,
+    },
+
+    /// `from` passou o `claim` para `to`
+    OwnershipTransferred {
+        from: T::AccountId,
+        to: T::AccountId,
+        claim: T::Content,
+    },
+
+    /// `owner` publicou uma nova versão do documento: o claim passou de `old` para `new`
+    ClaimUpdated {
+        owner: T::AccountId,
+        old: T::Content,
+        new: T::Content,
+        version: u32,
+    },
+
+    /// `attester` atestou o `claim` de outra conta
+    Attested {
+        attester: T::AccountId,
+        claim: T::Content,
+    },
+}
+
+/// Erros retornados pelo módulo de Prova de Existência
+#[macros::error]
+pub mod errors {
+    /// Já existe um claim com esse conteúdo, e ele ainda não expirou
+    pub const CLAIM_ALREADY_EXISTS: &str = "Claim already exists";
+
+    /// O conteúdo é uma versão antiga de um documento, substituída com `update_claim`
+    pub const CLAIM_SUPERSEDED: &str = "Claim was superseded";
+
+    /// Não existe um claim com esse conteúdo, ou ele já expirou
+    pub const CLAIM_NOT_FOUND: &str = "Claim não existe";
+
+    /// Só o dono do claim pode revogá-lo, transferi-lo ou atualizá-lo
+    pub const NOT_CLAIM_OWNER: &str = "Caller is not the owner of the claim";
+
+    /// O dono não pode atestar o próprio claim
+    pub const OWNER_CANNOT_ATTEST: &str = "Owner cannot attest own claim";
+
+    /// A conta já atestou esse claim
+    pub const ALREADY_ATTESTED: &str = "Claim already attested by caller";
+
+    /// O número da próxima versão do documento não cabe em um `u32`
+    pub const VERSION_OVERFLOW: &str = "Claim version overflow";
+
+    /// O novo dono do claim não tem saldo livre para o depósito
+    pub const CANNOT_RESERVE_DEPOSIT: &str = "Cannot reserve claim deposit";
 }
 
 /// esse é o módulo Prova de Existência
@@ -13,7 +142,28 @@ pub trait Config: crate::system::Config {
 pub struct Pallet<T: Config> {
     // Um `Content` pertence a uma `AccountId`,
     // e um `AccountId` por ter diversos `Content`
-    claims: BTreeMap<T::Content, T::AccountId>,
+    // (o tipo é escrito por extenso, e não com `ClaimDetailsOf`, para o `derive` funcionar)
+    #[allow(clippy::type_complexity)]
+    claims:
+        JournaledMap<T::Content, ClaimDetails<T::AccountId, T::BlockNumber, T::Amount, T::Content>>,
+
+    // índice reverso dos `claims`: os claims de cada dono.
+    // é mantido pelo `insert_claim` e pelo `remove_claim`
+    claims_by_owner: JournaledMap<T::AccountId, BTreeSet<T::Content>>,
+
+    // conteúdos substituídos por uma nova versão com `update_claim`.
+    // eles não podem virar claims de novo, senão outra conta ficaria com a versão
+    // para a qual o `previous` do documento aponta
+    superseded: JournaledMap<T::Content, ()>,
+
+    // bloco atual, recebido do runtime no `on_initialize`
+    block_number: T::BlockNumber,
+
+    // eventos emitidos durante a extrinsic atual, ainda não recolhidos pelo runtime
+    events: Vec<Event<T>>,
+
+    // depósitos a reservar ou devolver, ainda não aplicados pelo runtime
+    pending_deposits: Vec<DepositChange<T::AccountId, T::Amount>>,
 }
 
 /// implementamos o struct Pallet, mas apenas com as funções que queremos expor para uso.
@@ -21,16 +171,38 @@ pub struct Pallet<T: Config> {
 #[macros::call]
 impl<T: Config> Pallet<T> {
     /// Cria um novo claim (content, documento, file, etc) em nome do `Caller`
-    /// Retorna um erro se o alguém já criou um `claim` com o mesmo nome
+    /// Retorna um erro se o alguém já criou um `claim` com o mesmo nome,
+    /// ou se ele é uma versão antiga de um documento
     pub fn create_claim(&mut self, caller: T::AccountId, claim: T::Content) -> DispatchResult {
+        if self.superseded.contains_key(&claim) {
+            return Err(errors::CLAIM_SUPERSEDED);
+        }
+
         match self.get_claim(&claim) {
             // antes de criar um `claim` precisamos verificar se ele já não existe
-            Some(_) => Err("Claim already exists"),
+            // (um claim expirado conta como inexistente)
+            Some(_) => Err(errors::CLAIM_ALREADY_EXISTS),
 
             // se não há um `claim` igual ao informado, então inserimos no claims do pallet
             // e retornamos Ok(())
             None => {
-                self.claims.insert(claim, caller);
+                let deposit = T::ClaimDeposit::get();
+                self.change_deposit(DepositChange::Reserve(caller.clone(), deposit));
+                let details = ClaimDetails {
+                    owner: caller.clone(),
+                    created_at: self.block_number,
+                    deposit,
+                    attesters: BTreeSet::new(),
+                    version: 1,
+                    previous: None,
+                };
+                // um claim expirado que ainda não foi removido é substituído
+                self.discard_claim(&claim);
+                self.insert_claim(claim.clone(), details);
+                self.deposit_event(Event::ClaimCreated {
+                    owner: caller,
+                    claim,
+                });
                 Ok(())
             }
         }
@@ -40,49 +212,405 @@ impl<T: Config> Pallet<T> {
     /// Essa função só retornará sucesso se o o `caller` for o dono do `claim`
     pub fn revoke_claim(&mut self, caller: T::AccountId, claim: T::Content) -> DispatchResult {
         // se o `claim` não existir, lançamos um erro
-        let claim_owner = self.get_claim(&claim).ok_or("Claim não existe")?;
+        let claim_owner = self.get_claim(&claim).ok_or(errors::CLAIM_NOT_FOUND)?;
 
         // nesse ponto temos um `claim`, mas antes de removê-lo,
         // preciso garantir que o `caller` seja dono dele
         if claim_owner != &caller {
-            return Err("Caller is not the owner of the claim");
+            return Err(errors::NOT_CLAIM_OWNER);
         }
 
-        // Podemos remover o `claim`
-        self.claims.remove(&claim);
+        // Podemos remover o `claim`, e o depósito volta para o dono
+        self.discard_claim(&claim);
+        self.deposit_event(Event::ClaimRevoked {
+            owner: caller,
+            claim,
+        });
 
         // Tudo certo.
         Ok(())
     }
+
+    /// transfere um `claim` para outra conta, que passa a ser a dona dele.
+    /// Só o dono atual do `claim` pode transferi-lo
+    pub fn transfer_claim(
+        &mut self,
+        caller: T::AccountId,
+        claim: T::Content,
+        to: T::AccountId,
+    ) -> DispatchResult {
+        // se o `claim` não existir, lançamos um erro
+        let claim_owner = self.get_claim(&claim).ok_or(errors::CLAIM_NOT_FOUND)?;
+
+        // só o dono pode passar o `claim` adiante
+        if claim_owner != &caller {
+            return Err(errors::NOT_CLAIM_OWNER);
+        }
+
+        // o bloco de criação continua o mesmo. o depósito volta para o dono antigo
+        // e passa a ser reservado do novo dono
+        if let Some(mut details) = self.remove_claim(&claim) {
+            self.change_deposit(DepositChange::Release(caller.clone(), details.deposit));
+            self.change_deposit(DepositChange::Reserve(to.clone(), details.deposit));
+            details.owner = to.clone();
+            self.insert_claim(claim.clone(), details);
+        }
+        self.deposit_event(Event::OwnershipTransferred {
+            from: caller,
+            to,
+            claim,
+        });
+
+        Ok(())
+    }
+
+    /// publica uma nova versão de um documento: o claim `old` é substituído pelo `new`,
+    /// que guarda o número da versão e aponta para o `old`.
+    /// Só o dono de `old` pode atualizá-lo, e `new` não pode ser um claim existente
+    /// nem uma versão antiga. O `old` fica marcado como substituído e não pode ser criado de novo
+    pub fn update_claim(
+        &mut self,
+        caller: T::AccountId,
+        old: T::Content,
+        new: T::Content,
+    ) -> DispatchResult {
+        // se o `claim` não existir, lançamos um erro
+        let details = self.claim_details(&old).ok_or(errors::CLAIM_NOT_FOUND)?;
+
+        // só o dono pode publicar uma nova versão
+        if details.owner != caller {
+            return Err(errors::NOT_CLAIM_OWNER);
+        }
+        if self.get_claim(&new).is_some() {
+            return Err(errors::CLAIM_ALREADY_EXISTS);
+        }
+        if self.superseded.contains_key(&new) {
+            return Err(errors::CLAIM_SUPERSEDED);
+        }
+        let version = details
+            .version
+            .checked_add(1)
+            .ok_or(errors::VERSION_OVERFLOW)?;
+
+        // a nova versão é um documento novo: criada agora e ainda sem atestações
+        if let Some(mut details) = self.remove_claim(&old) {
+            details.created_at = self.block_number;
+            details.attesters.clear();
+            details.version = version;
+            details.previous = Some(old.clone());
+            self.discard_claim(&new);
+            self.insert_claim(new.clone(), details);
+        }
+        self.superseded.insert(old.clone(), ());
+        self.deposit_event(Event::ClaimUpdated {
+            owner: caller,
+            old,
+            new,
+            version,
+        });
+
+        Ok(())
+    }
+
+    /// atesta um `claim` de outra conta, ou seja, o `caller` confirma o documento junto com o dono.
+    /// As atestações continuam valendo se o claim for transferido, e somem com ele
+    pub fn attest(&mut self, caller: T::AccountId, claim: T::Content) -> DispatchResult {
+        // se o `claim` não existir, lançamos um erro
+        let claim_owner = self.get_claim(&claim).ok_or(errors::CLAIM_NOT_FOUND)?;
+
+        // o dono já responde pelo próprio claim
+        if claim_owner == &caller {
+            return Err(errors::OWNER_CANNOT_ATTEST);
+        }
+
+        let details = self.claims.get_mut(&claim).ok_or(errors::CLAIM_NOT_FOUND)?;
+        if !details.attesters.insert(caller.clone()) {
+            return Err(errors::ALREADY_ATTESTED);
+        }
+        self.deposit_event(Event::Attested {
+            attester: caller,
+            claim,
+        });
+
+        Ok(())
+    }
+
+    /// revoga todos os claims do `caller` de uma vez
+    pub fn revoke_all(&mut self, caller: T::AccountId) -> DispatchResult {
+        let claims: Vec<T::Content> = self.claims_of(&caller).into_iter().cloned().collect();
+
+        for claim in claims {
+            self.discard_claim(&claim);
+            self.deposit_event(Event::ClaimRevoked {
+                owner: caller.clone(),
+                claim,
+            });
+        }
+
+        Ok(())
+    }
 }
 
 impl<T: Config> Pallet<T> {
     pub fn new() -> Self {
         Self {
             // inicializamos o `claims`
-            claims: BTreeMap::new(),
+            claims: JournaledMap::new(),
+            claims_by_owner: JournaledMap::new(),
+            superseded: JournaledMap::new(),
+            block_number: T::BlockNumber::zero(),
+            events: Vec::new(),
+            pending_deposits: Vec::new(),
         }
     }
 
+    /// Cria um claim para o hash de `data`, em vez de guardar o conteúdo em si.
+    /// Qualquer um com os mesmos dados pode calcular o hash e verificar quem é o dono.
+    /// No runtime, os claims chegam pelas extrinsics, então só os testes criam claims assim
+    #[cfg(test)]
+    pub fn create_claim_from_bytes(&mut self, caller: T::AccountId, data: &[u8]) -> DispatchResult {
+        self.create_claim(caller, T::Hasher::hash(data))
+    }
+
     /// Recupera o owner do claim, se existir, caso contrário retorna null
     pub fn get_claim(&self, claim: &T::Content) -> Option<&T::AccountId> {
-        self.claims.get(&claim)
+        self.claim_details(claim).map(|details| &details.owner)
+    }
+
+    /// Recupera todas as informações do claim: dono, bloco de criação e depósito
+    pub fn claim_details(&self, claim: &T::Content) -> Option<&ClaimDetailsOf<T>> {
+        self.claims
+            .get(claim)
+            .filter(|details| !self.is_expired(details.created_at))
+    }
+
+    /// Lista as contas que atestaram o `claim`. Um claim inexistente não tem atestações
+    pub fn get_attestations(&self, claim: &T::Content) -> Vec<&T::AccountId> {
+        self.claim_details(claim)
+            .map(|details| details.attesters.iter().collect())
+            .unwrap_or_default()
+    }
+
+    /// Lista os claims de `owner`, sem os expirados
+    pub fn claims_of(&self, owner: &T::AccountId) -> Vec<&T::Content> {
+        self.claims_by_owner
+            .get(owner)
+            .into_iter()
+            .flatten()
+            .filter(|claim| self.claim_details(claim).is_some())
+            .collect()
+    }
+
+    /// Retira os eventos emitidos desde a última chamada.
+    /// O runtime chama essa função ao final de cada extrinsic
+    pub fn take_events(&mut self) -> Vec<Event<T>> {
+        std::mem::take(&mut self.events)
+    }
+
+    fn deposit_event(&mut self, event: Event<T>) {
+        self.events.push(event);
+    }
+
+    /// Reserva e devolve no `currency` os depósitos dos claims criados, transferidos,
+    /// revogados e expirados desde a última chamada. O runtime chama essa função depois de
+    /// cada extrinsic e no fim do bloco. Falha se o dono não tiver saldo para o depósito
+    pub fn settle_deposits(&mut self, currency: &mut T::Currency) -> DispatchResult {
+        for change in std::mem::take(&mut self.pending_deposits) {
+            match change {
+                DepositChange::Reserve(who, amount) => currency
+                    .reserve(&who, amount)
+                    .map_err(|_| errors::CANNOT_RESERVE_DEPOSIT)?,
+                DepositChange::Release(who, amount) => {
+                    currency.unreserve(&who, amount);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // guarda uma mudança de depósito para o runtime aplicar. depósitos zerados são ignorados
+    fn change_deposit(&mut self, change: DepositChange<T::AccountId, T::Amount>) {
+        let (DepositChange::Reserve(_, amount) | DepositChange::Release(_, amount)) = &change;
+        if !amount.is_zero() {
+            self.pending_deposits.push(change);
+        }
+    }
+
+    // remove o `claim` de vez, devolvendo o depósito ao dono
+    fn discard_claim(&mut self, claim: &T::Content) -> Option<ClaimDetailsOf<T>> {
+        let details = self.remove_claim(claim)?;
+        self.change_deposit(DepositChange::Release(
+            details.owner.clone(),
+            details.deposit,
+        ));
+        Some(details)
+    }
+
+    // guarda o `claim` e o adiciona ao índice do dono
+    fn insert_claim(&mut self, claim: T::Content, details: ClaimDetailsOf<T>) {
+        self.claims_by_owner
+            .get_or_default(details.owner.clone())
+            .insert(claim.clone());
+        self.claims.insert(claim, details);
+    }
+
+    // remove o `claim`, se existir, e o tira do índice do dono
+    fn remove_claim(&mut self, claim: &T::Content) -> Option<ClaimDetailsOf<T>> {
+        let details = self.claims.remove(claim)?;
+        if let Some(owned) = self.claims_by_owner.get_mut(&details.owner) {
+            owned.remove(claim);
+            if owned.is_empty() {
+                self.claims_by_owner.remove(&details.owner);
+            }
+        }
+        Some(details)
+    }
+
+    // um claim criado em `created_at` expira quando o bloco atual chega ao fim da sua validade.
+    // se o fim da validade não cabe no tipo `BlockNumber`, o claim nunca expira
+    fn is_expired(&self, created_at: T::BlockNumber) -> bool {
+        match T::CLAIM_LIFETIME.and_then(|lifetime| created_at.checked_add(&lifetime)) {
+            Some(expires_at) => self.block_number >= expires_at,
+            None => false,
+        }
+    }
+}
+
+impl<T: Config> EventTopics<T::AccountId> for Event<T> {
+    fn accounts(&self) -> Vec<&T::AccountId> {
+        match self {
+            Event::ClaimCreated { owner, .. }
+            | Event::ClaimRevoked { owner, .. }
+            | Event::ClaimExpired { owner, .. }
+            | Event::ClaimUpdated { owner, .. } => vec![owner],
+            Event::OwnershipTransferred { from, to, .. } => vec![from, to],
+            Event::Attested { attester, .. } => vec![attester],
+        }
+    }
+}
+
+// só os claims e os depósitos pendentes mudam durante um dispatch;
+// o número do bloco muda apenas no `on_initialize`
+impl<T: Config> Transactional for Pallet<T> {
+    fn begin(&mut self) {
+        self.claims.begin();
+        self.claims_by_owner.begin();
+        self.superseded.begin();
+    }
+
+    fn commit(&mut self) {
+        self.claims.commit();
+        self.claims_by_owner.commit();
+        self.superseded.commit();
+    }
+
+    // os depósitos pendentes são todos da extrinsic desfeita,
+    // já que o runtime os aplica depois de cada uma
+    fn rollback(&mut self) {
+        self.claims.rollback();
+        self.claims_by_owner.rollback();
+        self.superseded.rollback();
+        self.pending_deposits.clear();
     }
 }
 
+// no início de cada bloco, guardamos o número do bloco para registrar quando os claims são criados.
+// no fim do bloco, removemos os claims expirados
+impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+    fn on_initialize(&mut self, block_number: T::BlockNumber) {
+        self.block_number = block_number;
+    }
+
+    // o índice reverso precisa ter exatamente os claims de cada dono
+    // e nenhuma versão substituída pode estar guardada como claim
+    fn try_state(&self) -> DispatchResult {
+        let indexed: usize = self.claims_by_owner.values().map(BTreeSet::len).sum();
+        let consistent = indexed == self.claims.len()
+            && self.claims_by_owner.iter().all(|(owner, owned)| {
+                owned.iter().all(|claim| {
+                    self.claims.get(claim).map(|details| &details.owner) == Some(owner)
+                })
+            });
+        if !consistent {
+            return Err("Claims by owner index does not match the claims");
+        }
+
+        // uma versão substituída nunca volta a ser um claim
+        if self
+            .superseded
+            .keys()
+            .any(|claim| self.claims.contains_key(claim))
+        {
+            return Err("Superseded claim is still stored");
+        }
+        Ok(())
+    }
 
+    fn on_finalize(&mut self, _block_number: T::BlockNumber) {
+        let expired: Vec<T::Content> = self
+            .claims
+            .iter()
+            .filter(|(_, details)| self.is_expired(details.created_at))
+            .map(|(claim, _)| claim.clone())
+            .collect();
+
+        for claim in expired {
+            if let Some(details) = self.discard_claim(&claim) {
+                self.deposit_event(Event::ClaimExpired {
+                    owner: details.owner,
+                    claim,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
 mod tests {
+    use super::{ClaimDetails, Event};
+    use crate::support::{ConstU32, Hasher, Hooks, Sha256};
 
+    #[derive(Debug, PartialEq)]
     struct TestConfig;
 
+    // nos testes, o conteúdo dos claims é o SHA-256 em hexadecimal, para podermos usar strings
+    struct HexSha256;
+
+    impl Hasher for HexSha256 {
+        type Output = String;
+
+        fn hash(data: &[u8]) -> String {
+            format!("{:?}", Sha256::hash(data))
+        }
+    }
+
     impl super::Config for TestConfig {
         type Content = String;
+        type Hasher = HexSha256;
+        const CLAIM_LIFETIME: Option<u32> = Some(10);
+        type Currency = crate::balances::Pallet<TestConfig>;
+        type ClaimDeposit = ConstU32<10>;
+    }
+
+    impl crate::balances::Config for TestConfig {
+        type AccountId = String;
+        type Amount = u32;
+        const EXISTENTIAL_DEPOSIT: u32 = 1;
+        type MinimumTransfer = ConstU32<1>;
+        const MAX_MEMO_LENGTH: u32 = 8;
+        const ACCRUAL: Option<crate::balances::Accrual> = None;
+        type OnDeposit = ();
+        type OnWithdraw = ();
+        type OnDust = ();
     }
 
     impl crate::system::Config for TestConfig {
         type BlockNumber = u32;
         type AccountId = String;
         type Nonce = u32;
+        type Amount = u32;
+        const MAX_EXTRINSICS_PER_BLOCK: u32 = 10;
+        type RuntimeEvent = ();
     }
 
     #[test]
@@ -120,5 +648,450 @@ mod tests {
         let result = poe.revoke_claim("lucio".to_string(), "my_code".to_string());
         assert_eq!(result, Ok(())); // verificamos se o retorno é `Ok(())`
         assert_eq!(poe.get_claim(&"my_code".to_string()), None); // verificamos se depois de removido é `None`
+
+        // --- Apenas as chamadas bem-sucedidas emitem eventos ---//
+        assert_eq!(
+            poe.take_events(),
+            vec![
+                Event::ClaimCreated {
+                    owner: "lucio".to_string(),
+                    claim: "my_code".to_string()
+                },
+                Event::ClaimRevoked {
+                    owner: "lucio".to_string(),
+                    claim: "my_code".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn transfer_claim() {
+        let mut poe = super::Pallet::<TestConfig>::new();
+        let _ = poe.create_claim("lucio".to_string(), "my_code".to_string());
+        poe.take_events();
+
+        // --- Teste em que tenta transferir um claim que não existe ---//
+        let result = poe.transfer_claim(
+            "lucio".to_string(),
+            "outro_code".to_string(),
+            "miriam".to_string(),
+        );
+        assert_eq!(result, Err("Claim não existe"));
+
+        // --- Teste em que miriam tenta transferir um claim que não é dela ---//
+        let result = poe.transfer_claim(
+            "miriam".to_string(),
+            "my_code".to_string(),
+            "miriam".to_string(),
+        );
+        assert_eq!(result, Err("Caller is not the owner of the claim"));
+        assert_eq!(
+            poe.get_claim(&"my_code".to_string()),
+            Some(&"lucio".to_string())
+        );
+
+        // --- Teste de transferência do `claim` -----//
+        let result = poe.transfer_claim(
+            "lucio".to_string(),
+            "my_code".to_string(),
+            "miriam".to_string(),
+        );
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            poe.get_claim(&"my_code".to_string()),
+            Some(&"miriam".to_string())
+        );
+
+        // agora só a miriam pode revogar o claim
+        let result = poe.revoke_claim("lucio".to_string(), "my_code".to_string());
+        assert_eq!(result, Err("Caller is not the owner of the claim"));
+
+        assert_eq!(
+            poe.take_events(),
+            vec![Event::OwnershipTransferred {
+                from: "lucio".to_string(),
+                to: "miriam".to_string(),
+                claim: "my_code".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn claims_by_owner() {
+        let mut poe = super::Pallet::<TestConfig>::new();
+        let (lucio, miriam) = ("lucio".to_string(), "miriam".to_string());
+
+        let _ = poe.create_claim(lucio.clone(), "a".to_string());
+        let _ = poe.create_claim(lucio.clone(), "b".to_string());
+        let _ = poe.create_claim(miriam.clone(), "c".to_string());
+        assert_eq!(poe.claims_of(&lucio), vec!["a", "b"]);
+        assert_eq!(poe.claims_of(&miriam), vec!["c"]);
+
+        // o índice acompanha as transferências e revogações
+        let _ = poe.transfer_claim(lucio.clone(), "b".to_string(), miriam.clone());
+        let _ = poe.revoke_claim(miriam.clone(), "c".to_string());
+        assert_eq!(poe.claims_of(&lucio), vec!["a"]);
+        assert_eq!(poe.claims_of(&miriam), vec!["b"]);
+        assert_eq!(poe.try_state(), Ok(()));
+        poe.take_events();
+
+        // revoke_all remove só os claims do caller
+        assert_eq!(poe.revoke_all(miriam.clone()), Ok(()));
+        assert_eq!(poe.claims_of(&miriam), Vec::<&String>::new());
+        assert_eq!(poe.get_claim(&"a".to_string()), Some(&lucio));
+        assert_eq!(
+            poe.take_events(),
+            vec![Event::ClaimRevoked {
+                owner: miriam.clone(),
+                claim: "b".to_string()
+            }]
+        );
+
+        // claims expirados somem do índice
+        poe.on_initialize(10);
+        poe.on_finalize(10);
+        assert_eq!(poe.claims_of(&lucio), Vec::<&String>::new());
+        assert_eq!(poe.try_state(), Ok(()));
+    }
+
+    #[test]
+    fn attestations() {
+        let mut poe = super::Pallet::<TestConfig>::new();
+        let (lucio, miriam, ana) = ("lucio".to_string(), "miriam".to_string(), "ana".to_string());
+        let claim = "contrato".to_string();
+        let _ = poe.create_claim(lucio.clone(), claim.clone());
+        poe.take_events();
+
+        assert_eq!(
+            poe.attest(miriam.clone(), "outro".to_string()),
+            Err("Claim não existe")
+        );
+        assert_eq!(
+            poe.attest(lucio.clone(), claim.clone()),
+            Err("Owner cannot attest own claim")
+        );
+
+        // várias contas podem atestar o mesmo claim, cada uma uma vez
+        assert_eq!(poe.attest(miriam.clone(), claim.clone()), Ok(()));
+        assert_eq!(poe.attest(ana.clone(), claim.clone()), Ok(()));
+        assert_eq!(
+            poe.attest(miriam.clone(), claim.clone()),
+            Err("Claim already attested by caller")
+        );
+        assert_eq!(poe.get_attestations(&claim), vec![&ana, &miriam]);
+        assert_eq!(
+            poe.take_events(),
+            vec![
+                Event::Attested {
+                    attester: miriam.clone(),
+                    claim: claim.clone()
+                },
+                Event::Attested {
+                    attester: ana.clone(),
+                    claim: claim.clone()
+                },
+            ]
+        );
+
+        // as atestações acompanham o claim quando ele é transferido...
+        let _ = poe.transfer_claim(lucio.clone(), claim.clone(), ana.clone());
+        assert_eq!(poe.get_attestations(&claim), vec![&ana, &miriam]);
+
+        // ...e somem quando ele é revogado
+        let _ = poe.revoke_claim(ana.clone(), claim.clone());
+        let _ = poe.create_claim(lucio, claim.clone());
+        assert_eq!(poe.get_attestations(&claim), Vec::<&String>::new());
+    }
+
+    #[test]
+    fn update_claim() {
+        let mut poe = super::Pallet::<TestConfig>::new();
+        let (lucio, miriam) = ("lucio".to_string(), "miriam".to_string());
+        let (v1, v2, v3) = ("v1".to_string(), "v2".to_string(), "v3".to_string());
+        let _ = poe.create_claim(lucio.clone(), v1.clone());
+        let _ = poe.create_claim(miriam.clone(), v3.clone());
+        let _ = poe.attest(miriam.clone(), v1.clone());
+        poe.take_events();
+
+        assert_eq!(
+            poe.update_claim(lucio.clone(), "outro".to_string(), v2.clone()),
+            Err("Claim não existe")
+        );
+        assert_eq!(
+            poe.update_claim(miriam.clone(), v1.clone(), v2.clone()),
+            Err("Caller is not the owner of the claim")
+        );
+        assert_eq!(
+            poe.update_claim(lucio.clone(), v1.clone(), v3.clone()),
+            Err("Claim already exists")
+        );
+
+        // a nova versão aponta para a anterior, que deixa de ser um claim
+        poe.on_initialize(2);
+        assert_eq!(
+            poe.update_claim(lucio.clone(), v1.clone(), v2.clone()),
+            Ok(())
+        );
+        assert_eq!(poe.get_claim(&v1), None);
+        assert_eq!(
+            poe.claim_details(&v2),
+            Some(&ClaimDetails {
+                owner: lucio.clone(),
+                created_at: 2,
+                deposit: 10,
+                attesters: Default::default(),
+                version: 2,
+                previous: Some(v1.clone()),
+            })
+        );
+        assert_eq!(poe.claims_of(&lucio), vec![&v2]);
+        assert_eq!(poe.try_state(), Ok(()));
+        assert_eq!(
+            poe.take_events(),
+            vec![Event::ClaimUpdated {
+                owner: lucio.clone(),
+                old: v1.clone(),
+                new: v2.clone(),
+                version: 2
+            }]
+        );
+
+        // a versão antiga não pode ser criada por outra conta, para ninguém se apropriar
+        // do documento para o qual o `previous` aponta, nem voltar como nova versão
+        assert_eq!(
+            poe.create_claim(miriam.clone(), v1.clone()),
+            Err("Claim was superseded")
+        );
+        assert_eq!(
+            poe.update_claim(lucio.clone(), v2.clone(), v1.clone()),
+            Err("Claim was superseded")
+        );
+
+        // nem depois que a nova versão deixa de existir
+        assert_eq!(poe.revoke_claim(lucio, v2), Ok(()));
+        assert_eq!(
+            poe.create_claim(miriam, v1.clone()),
+            Err("Claim was superseded")
+        );
+        assert_eq!(poe.get_claim(&v1), None);
+    }
+
+    #[test]
+    fn claims_from_bytes() {
+        let mut poe = super::Pallet::<TestConfig>::new();
+        let document = b"contrato assinado";
+
+        // o claim guarda o hash do documento
+        assert_eq!(
+            poe.create_claim_from_bytes("lucio".to_string(), document),
+            Ok(())
+        );
+        assert_eq!(
+            poe.get_claim(&HexSha256::hash(document)),
+            Some(&"lucio".to_string())
+        );
+
+        // o mesmo documento gera o mesmo hash, então não pode ser registrado de novo
+        assert_eq!(
+            poe.create_claim_from_bytes("miriam".to_string(), document),
+            Err("Claim already exists")
+        );
+        assert_eq!(
+            poe.create_claim_from_bytes("miriam".to_string(), b"outro contrato"),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn claim_details() {
+        let mut poe = super::Pallet::<TestConfig>::new();
+
+        poe.on_initialize(3);
+        assert_eq!(
+            poe.create_claim("lucio".to_string(), "my_code".to_string()),
+            Ok(())
+        );
+
+        // o claim guarda o bloco em que foi criado, mesmo depois de outros blocos
+        poe.on_initialize(4);
+        assert_eq!(
+            poe.claim_details(&"my_code".to_string()),
+            Some(&ClaimDetails {
+                owner: "lucio".to_string(),
+                created_at: 3,
+                deposit: 10,
+                attesters: Default::default(),
+                version: 1,
+                previous: None,
+            })
+        );
+        assert_eq!(poe.claim_details(&"outro_code".to_string()), None);
+    }
+
+    #[test]
+    fn claim_deposits() {
+        let mut poe = super::Pallet::<TestConfig>::new();
+        let mut balances = crate::balances::Pallet::<TestConfig>::new();
+        let (lucio, miriam) = ("lucio".to_string(), "miriam".to_string());
+        let (a, b) = ("a".to_string(), "b".to_string());
+        balances.set_balance(&lucio, 100).unwrap();
+        balances.set_balance(&miriam, 15).unwrap();
+
+        // cada claim criado reserva o depósito do dono
+        poe.on_initialize(1);
+        assert_eq!(poe.create_claim(lucio.clone(), a.clone()), Ok(()));
+        assert_eq!(poe.create_claim(lucio.clone(), b.clone()), Ok(()));
+        assert_eq!(poe.settle_deposits(&mut balances), Ok(()));
+        assert_eq!(balances.reserved_balance(&lucio), 20);
+        assert_eq!(balances.get_balance(&lucio), 80);
+        assert_eq!(
+            poe.claim_details(&a).map(|details| details.deposit),
+            Some(10)
+        );
+
+        // revogar o claim devolve o depósito
+        assert_eq!(poe.revoke_claim(lucio.clone(), a.clone()), Ok(()));
+        assert_eq!(poe.settle_deposits(&mut balances), Ok(()));
+        assert_eq!(balances.reserved_balance(&lucio), 10);
+        assert_eq!(balances.get_balance(&lucio), 90);
+
+        // ao transferir o claim, o depósito passa a ser do novo dono
+        assert_eq!(
+            poe.transfer_claim(lucio.clone(), b.clone(), miriam.clone()),
+            Ok(())
+        );
+        assert_eq!(poe.settle_deposits(&mut balances), Ok(()));
+        assert_eq!(balances.reserved_balance(&lucio), 0);
+        assert_eq!(balances.reserved_balance(&miriam), 10);
+        assert_eq!(balances.get_balance(&miriam), 5);
+
+        // um claim expirado também devolve o depósito
+        poe.on_initialize(11);
+        poe.on_finalize(11);
+        assert_eq!(poe.settle_deposits(&mut balances), Ok(()));
+        assert_eq!(balances.reserved_balance(&miriam), 0);
+        assert_eq!(balances.get_balance(&miriam), 15);
+
+        // sem saldo para o depósito, a reserva falha
+        assert_eq!(poe.create_claim("pedro".to_string(), a.clone()), Ok(()));
+        assert_eq!(
+            poe.settle_deposits(&mut balances),
+            Err("Cannot reserve claim deposit")
+        );
+        assert_eq!(balances.total_issuance(), 115);
+    }
+
+    #[test]
+    fn claims_expire() {
+        let mut poe = super::Pallet::<TestConfig>::new();
+
+        poe.on_initialize(1);
+        let _ = poe.create_claim("lucio".to_string(), "my_code".to_string());
+        poe.take_events();
+
+        // o claim ainda vale no último bloco da sua validade
+        poe.on_initialize(10);
+        poe.on_finalize(10);
+        assert_eq!(
+            poe.get_claim(&"my_code".to_string()),
+            Some(&"lucio".to_string())
+        );
+        assert_eq!(poe.take_events(), vec![]);
+
+        // no bloco 11 o claim expirou: ninguém mais é dono dele, e qualquer um pode criá-lo de novo
+        poe.on_initialize(11);
+        assert_eq!(poe.get_claim(&"my_code".to_string()), None);
+        assert_eq!(
+            poe.create_claim("miriam".to_string(), "my_code".to_string()),
+            Ok(())
+        );
+        assert_eq!(
+            poe.claim_details(&"my_code".to_string())
+                .map(|details| details.created_at),
+            Some(11)
+        );
+
+        // o claim novo não é removido no fim do bloco
+        poe.on_finalize(11);
+        assert_eq!(
+            poe.get_claim(&"my_code".to_string()),
+            Some(&"miriam".to_string())
+        );
+
+        // se ninguém recriar o claim, ele é removido no fim do bloco
+        poe.on_initialize(21);
+        poe.on_finalize(21);
+        assert_eq!(poe.claim_details(&"my_code".to_string()), None);
+        assert_eq!(
+            poe.take_events(),
+            vec![
+                Event::ClaimCreated {
+                    owner: "miriam".to_string(),
+                    claim: "my_code".to_string()
+                },
+                Event::ClaimExpired {
+                    owner: "miriam".to_string(),
+                    claim: "my_code".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn call_metadata() {
+        let metadata = super::Call::<TestConfig>::metadata();
+        let names: Vec<_> = metadata.iter().map(|call| call.name).collect();
+        assert_eq!(
+            names,
+            vec![
+                "create_claim",
+                "revoke_claim",
+                "transfer_claim",
+                "update_claim",
+                "attest",
+                "revoke_all"
+            ]
+        );
+
+        // os comentários de documentação das funções viram a descrição das chamadas
+        assert_eq!(
+            metadata[0].docs[0],
+            "Cria um novo claim (content, documento, file, etc) em nome do `Caller`"
+        );
+    }
+
+    #[test]
+    fn event_metadata() {
+        let metadata = super::Event::<TestConfig>::metadata();
+        let names: Vec<_> = metadata.iter().map(|event| event.name).collect();
+        assert_eq!(
+            names,
+            vec![
+                "ClaimCreated",
+                "ClaimRevoked",
+                "ClaimExpired",
+                "OwnershipTransferred",
+                "ClaimUpdated",
+                "Attested"
+            ]
+        );
+
+        // assim como nas chamadas, a documentação vem dos comentários das variantes
+        assert_eq!(metadata[0].docs, &["`owner` criou o `claim`"]);
+    }
+
+    #[test]
+    fn error_metadata() {
+        let metadata = super::errors::metadata();
+        assert_eq!(metadata.len(), 8);
+
+        // cada erro é descrito pela mensagem retornada, com os comentários da constante
+        assert_eq!(metadata[0].message, super::errors::CLAIM_ALREADY_EXISTS);
+        assert_eq!(metadata[0].message, "Claim already exists");
+        assert_eq!(
+            metadata[0].docs,
+            &["Já existe um claim com esse conteúdo, e ele ainda não expirou"]
+        );
     }
 }
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 /// A representação mais básica de um bloco em nossa blockchain
 pub struct Block<Header, Extrinsic> {
     /// O cabeçalho do bloco contém metadados sobre o bloco, como número e hash
@@ -17,19 +19,128 @@ pub struct Header<BlockNumber> {
 /// Isto é uma 'extrinsic': uma mensagem externa que vem de fora da blockchain.
 /// Esta versão simplificada de uma extrinsic nos informa quem está fazendo a chamada
 /// e qual chamada está sendo feita
-pub struct Extrinsic<Caller, Call> {
+pub struct Extrinsic<Caller, Call, Amount> {
     /// O endereço ou identificador de quem está fazendo a chamada
     pub caller: Caller,
     /// A ação ou função que está sendo chamada
     pub call: Call,
+    /// Gorjeta opcional paga além da taxa base, para dar prioridade à extrinsic
+    pub tip: Option<Amount>,
 }
 
 /// O tipo de resultado do nosso runtime. Quando tudo é concluído com sucesso,
 /// retornamos 'Ok(())', caso contrário, retornamos uma mensagem de erro estática
 pub type DispatchResult = Result<(), &'static str>;
 
+/// Um erro de dispatch acompanhado do contexto em que aconteceu.
+/// O runtime preenche o pallet e a chamada que falharam, e assim um erro como
+/// "Insufficient balance" é exibido como `balances.transfer: Insufficient balance`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DispatchError {
+    /// O pallet onde o erro aconteceu, se o erro veio de uma chamada
+    pub pallet: Option<&'static str>,
+
+    /// A chamada do pallet que falhou
+    pub call: Option<&'static str>,
+
+    /// A mensagem de erro retornada
+    pub error: &'static str,
+}
+
+// um erro sem contexto, por exemplo quando a extrinsic nem chegou a ser executada
+impl From<&'static str> for DispatchError {
+    fn from(error: &'static str) -> Self {
+        DispatchError {
+            pallet: None,
+            call: None,
+            error,
+        }
+    }
+}
+
+impl std::fmt::Display for DispatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.pallet, self.call) {
+            (Some(pallet), Some(call)) => write!(f, "{}.{}: {}", pallet, call, self.error),
+            (Some(pallet), None) => write!(f, "{}: {}", pallet, self.error),
+            _ => write!(f, "{}", self.error),
+        }
+    }
+}
+
+/// Descrição de uma chamada de um pallet, gerada pelo `#[macros::call]`
+/// a partir dos comentários de documentação da função
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallMetadata {
+    /// O nome da chamada, igual ao nome da função no pallet
+    pub name: &'static str,
+
+    /// As linhas do comentário de documentação da função
+    pub docs: &'static [&'static str],
+}
+
+/// Descrição de um evento de um pallet, gerada pelo `#[macros::event]`
+/// a partir dos comentários de documentação da variante
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventMetadata {
+    /// O nome do evento, igual ao nome da variante no enum `Event`
+    pub name: &'static str,
+
+    /// As linhas do comentário de documentação da variante
+    pub docs: &'static [&'static str],
+}
+
+/// Descrição de um erro de um pallet, gerada pelo `#[macros::error]`
+/// a partir dos comentários de documentação da constante
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorMetadata {
+    /// A mensagem do erro, igual à retornada pelas chamadas
+    pub message: &'static str,
+
+    /// As linhas do comentário de documentação da constante
+    pub docs: &'static [&'static str],
+}
+
+/// Descrição de um pallet do runtime, das chamadas que ele expõe, dos eventos que emite
+/// e dos erros que retorna. É gerada pelo `#[macros::runtime]` em `RuntimeCall::metadata()`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PalletMetadata {
+    pub name: &'static str,
+    pub calls: Vec<CallMetadata>,
+    pub events: Vec<EventMetadata>,
+    pub errors: Vec<ErrorMetadata>,
+}
+
+/// A origem de uma chamada, ou seja, em nome de quem ela está sendo executada
+#[derive(Debug, Clone, PartialEq)]
+pub enum Origin<AccountId> {
+    /// Origem privilegiada, usada pelo próprio sistema (gênesis, governança, sudo).
+    /// Nenhuma extrinsic enviada por usuários tem essa origem
+    Root,
+
+    /// Chamada assinada por uma conta
+    Signed(AccountId),
+}
+
+/// Garante que a origem é uma conta assinada, retornando essa conta
+pub fn ensure_signed<AccountId>(origin: Origin<AccountId>) -> Result<AccountId, &'static str> {
+    match origin {
+        Origin::Signed(account) => Ok(account),
+        Origin::Root => Err("BadOrigin"),
+    }
+}
+
+/// Garante que a origem é `Root`
+pub fn ensure_root<AccountId>(origin: Origin<AccountId>) -> DispatchResult {
+    match origin {
+        Origin::Root => Ok(()),
+        Origin::Signed(_) => Err("BadOrigin"),
+    }
+}
+
 pub trait Dispatch {
-    /// O tipo usado para identificar quem está fazendo a chamada
+    /// O tipo usado para identificar quem está fazendo a chamada.
+    /// No runtime e nos pallets, é a `Origin` da chamada
     type Caller;
 
     /// O tipo que representa a função de transição de estado que o chamador está tentando acessar
@@ -40,3 +151,743 @@ pub trait Dispatch {
     /// Ela é responsável por executar a lógica da transação.
     fn dispatch(&mut self, caller: Self::Caller, call: Self::Call) -> DispatchResult;
 }
+
+/// Interface de uma moeda. Os pallets que movem fundos dependem dela em vez de depender
+/// diretamente do `balances`, o que também permite usar moedas de mentira nos testes
+pub trait Currency<AccountId, Amount> {
+    /// Saldo livre de `who`
+    fn free_balance(&self, who: &AccountId) -> Amount;
+
+    /// Total de moedas em circulação
+    fn total_issuance(&self) -> Amount;
+
+    /// Transfere `amount` do saldo livre de `from` para `to`
+    fn transfer(&mut self, from: &AccountId, to: &AccountId, amount: Amount) -> DispatchResult;
+
+    /// Retira `amount` do saldo livre de `who` e destrói esse valor
+    fn withdraw(&mut self, who: &AccountId, amount: Amount) -> DispatchResult;
+
+    /// Cria `amount` no saldo livre de `who`. É o par de `withdraw`, para creditar um valor
+    /// retirado de outra conta, e por isso não passa pelas verificações de uma transferência
+    fn deposit(&mut self, who: &AccountId, amount: Amount) -> DispatchResult;
+
+    /// Move `amount` do saldo livre de `who` para o saldo reservado
+    fn reserve(&mut self, who: &AccountId, amount: Amount) -> DispatchResult;
+
+    /// Devolve até `amount` do saldo reservado de `who` para o saldo livre.
+    /// Retorna o que não pôde ser devolvido
+    fn unreserve(&mut self, who: &AccountId, amount: Amount) -> Amount;
+
+    /// Corta até `amount` de `who`, retornando `(cortado, restante)`
+    fn slash(&mut self, who: &AccountId, amount: Amount) -> (Amount, Amount);
+}
+
+/// Ganchos chamados pelo runtime em todos os pallets no início e no fim de cada bloco.
+/// Os métodos já têm uma implementação vazia, então cada pallet só sobrescreve o que precisar
+pub trait Hooks<BlockNumber> {
+    /// Chamado no início do bloco, antes das extrinsics
+    fn on_initialize(&mut self, _block_number: BlockNumber) {}
+
+    /// Chamado no fim do bloco, depois de todas as extrinsics
+    fn on_finalize(&mut self, _block_number: BlockNumber) {}
+
+    /// Verifica os invariantes do estado do pallet, retornando um erro se algum foi violado.
+    /// O runtime chama essa função ao final de cada bloco em builds de debug (e nos testes)
+    fn try_state(&self) -> DispatchResult {
+        Ok(())
+    }
+}
+
+/// Cobrança feita pelo runtime antes de executar cada extrinsic, como as taxas.
+/// Se a cobrança falhar, a extrinsic é rejeitada sem ser executada
+pub trait ChargeTransaction {
+    /// Quem assinou a extrinsic e vai pagar por ela
+    type Caller;
+
+    /// A chamada que vai ser executada
+    type Call;
+
+    /// O tipo da gorjeta que pode acompanhar a extrinsic
+    type Tip;
+
+    /// Cobra `caller` por executar `call`, incluindo a gorjeta `tip`, se houver
+    fn charge_transaction(
+        &mut self,
+        caller: &Self::Caller,
+        call: &Self::Call,
+        tip: Option<Self::Tip>,
+    ) -> DispatchResult;
+}
+
+/// Efeitos que um pallet pede em outro, como reservar no balances o depósito de um claim.
+/// Como um pallet não acessa os outros, o runtime aplica esses pedidos depois de cada dispatch
+/// bem-sucedido e depois do `on_finalize`. Se falhar durante uma extrinsic, ela falha e tudo o
+/// que mudou desde o dispatch é desfeito
+pub trait Settle {
+    fn settle(&mut self) -> DispatchResult;
+}
+
+/// Estado que pode ser desfeito. Antes de cada dispatch o runtime chama `begin` em todos os
+/// pallets e, se a chamada entrar em pânico, `rollback` volta cada um ao estado do `begin`.
+/// Não há transações aninhadas: um novo `begin` descarta o que foi guardado pelo anterior
+pub trait Transactional {
+    /// Começa a guardar o necessário para desfazer as próximas mudanças
+    fn begin(&mut self);
+
+    /// Mantém as mudanças feitas desde o `begin`
+    fn commit(&mut self);
+
+    /// Desfaz as mudanças feitas desde o `begin`
+    fn rollback(&mut self);
+}
+
+/// Um `BTreeMap` que guarda, durante uma transação, o valor antigo de cada chave alterada.
+/// Assim o `rollback` desfaz só o que mudou, sem que o runtime precise clonar o mapa inteiro.
+/// As leituras vão direto para o mapa, via `Deref`; as escritas passam pelos métodos abaixo
+#[derive(Debug, Clone)]
+pub struct JournaledMap<K, V> {
+    map: BTreeMap<K, V>,
+
+    // o valor de cada chave alterada desde o `begin` (`None` se ela não existia).
+    // fica `None` quando não há transação aberta
+    journal: Option<BTreeMap<K, Option<V>>>,
+}
+
+impl<K: Ord + Clone, V: Clone> JournaledMap<K, V> {
+    pub fn new() -> Self {
+        Self {
+            map: BTreeMap::new(),
+            journal: None,
+        }
+    }
+
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.note(&key);
+        self.map.insert(key, value)
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        if self.map.contains_key(key) {
+            self.note(key);
+        }
+        self.map.remove(key)
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        if self.map.contains_key(key) {
+            self.note(key);
+        }
+        self.map.get_mut(key)
+    }
+
+    /// O valor de `key`, criando-o com `Default` se ainda não existir
+    pub fn get_or_default(&mut self, key: K) -> &mut V
+    where
+        V: Default,
+    {
+        self.note(&key);
+        self.map.entry(key).or_default()
+    }
+
+    /// Mantém só as entradas para as quais `keep` retorna `true`
+    pub fn retain(&mut self, mut keep: impl FnMut(&K, &V) -> bool) {
+        let removed: Vec<K> = self
+            .map
+            .iter()
+            .filter(|(key, value)| !keep(key, value))
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in removed {
+            self.remove(&key);
+        }
+    }
+
+    // guarda o valor atual de `key`, se houver uma transação aberta e ele ainda não foi guardado
+    fn note(&mut self, key: &K) {
+        if let Some(journal) = &mut self.journal {
+            if !journal.contains_key(key) {
+                journal.insert(key.clone(), self.map.get(key).cloned());
+            }
+        }
+    }
+}
+
+impl<K: Ord + Clone, V: Clone> Default for JournaledMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> std::ops::Deref for JournaledMap<K, V> {
+    type Target = BTreeMap<K, V>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.map
+    }
+}
+
+impl<K: Ord, V> Transactional for JournaledMap<K, V> {
+    fn begin(&mut self) {
+        self.journal = Some(BTreeMap::new());
+    }
+
+    fn commit(&mut self) {
+        self.journal = None;
+    }
+
+    fn rollback(&mut self) {
+        for (key, old) in self.journal.take().into_iter().flatten() {
+            match old {
+                Some(value) => self.map.insert(key, value),
+                None => self.map.remove(&key),
+            };
+        }
+    }
+}
+
+/// Identificador de um pallet, por exemplo `PalletId(*b"py/trsry")`.
+/// Serve para derivar a conta própria do pallet, que pode guardar saldo como qualquer outra
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PalletId(pub [u8; 8]);
+
+impl PalletId {
+    /// A conta do pallet: sempre a mesma para o mesmo identificador,
+    /// e diferente para identificadores diferentes.
+    /// É o prefixo `modl` seguido dos bytes do identificador em hexadecimal
+    pub fn into_account<AccountId: From<String>>(self) -> AccountId {
+        let hex: String = self.0.iter().map(|byte| format!("{:02x}", byte)).collect();
+        AccountId::from(format!("modl{}", hex))
+    }
+}
+
+/// Um valor constante definido pelo runtime, como um parâmetro de configuração de um pallet.
+/// Os pallets declaram um tipo associado `type X: Get<Valor>` e leem o valor com `T::X::get()`
+pub trait Get<T> {
+    fn get() -> T;
+}
+
+/// Implementação de `Get` para constantes numéricas, por exemplo `type X = ConstU32<10>`.
+/// Serve para qualquer tipo que possa ser criado a partir de um `u32`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConstU32<const N: u32>;
+
+impl<T: From<u32>, const N: u32> Get<T> for ConstU32<N> {
+    fn get() -> T {
+        T::from(N)
+    }
+}
+
+/// As contas envolvidas em um evento, usadas para indexar os eventos por conta.
+/// Todo evento de pallet implementa essa trait; o `RuntimeEvent` gerado pelo `#[macros::runtime]`
+/// repassa a pergunta para o evento do pallet
+pub trait EventTopics<AccountId> {
+    /// As contas citadas no evento. Por padrão, nenhuma
+    fn accounts(&self) -> Vec<&AccountId> {
+        Vec::new()
+    }
+}
+
+// eventos de mentira, usados nos testes dos pallets
+impl<AccountId> EventTopics<AccountId> for () {}
+
+/// Filtro de Bloom de 256 bits: um resumo de um conjunto que responde
+/// "com certeza não contém" ou "talvez contenha" (pode haver falsos positivos, mas não negativos)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BloomFilter {
+    bits: [u64; 4],
+}
+
+impl BloomFilter {
+    // quantos bits são marcados para cada item
+    const HASHES: usize = 3;
+
+    /// Adiciona `item` ao filtro
+    pub fn insert(&mut self, item: &[u8]) {
+        for bit in Self::bits_of(item) {
+            self.bits[bit / 64] |= 1 << (bit % 64);
+        }
+    }
+
+    /// Retorna `false` se `item` com certeza não foi adicionado ao filtro
+    pub fn contains(&self, item: &[u8]) -> bool {
+        Self::bits_of(item).all(|bit| self.bits[bit / 64] & (1 << (bit % 64)) != 0)
+    }
+
+    // os bits de `item` são os primeiros bytes do seu SHA-256 (cada byte escolhe um dos 256 bits),
+    // então o filtro de um bloco é o mesmo em qualquer máquina
+    fn bits_of(item: &[u8]) -> impl Iterator<Item = usize> {
+        let hash = Sha256::hash(item);
+        (0..Self::HASHES).map(move |i| hash.0[i] as usize)
+    }
+}
+
+/// Um hash de 32 bytes, como os usados para identificar conteúdos sem guardá-los na chain
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct H256(pub [u8; 32]);
+
+// exibimos o hash em hexadecimal, que é como ele costuma aparecer
+impl std::fmt::Debug for H256 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "0x")?;
+        self.0.iter().try_for_each(|byte| write!(f, "{:02x}", byte))
+    }
+}
+
+/// Função de hash usada pelos pallets. Com ela, o pallet guarda só o hash de um conteúdo,
+/// e o conteúdo em si fica com o usuário
+pub trait Hasher {
+    /// O tipo do hash gerado
+    type Output;
+
+    /// Calcula o hash de `data`
+    fn hash(data: &[u8]) -> Self::Output;
+}
+
+/// O SHA-256, um hasher com saída de 32 bytes (implementado pelo crate `sha2`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sha256;
+
+impl Hasher for Sha256 {
+    type Output = H256;
+
+    fn hash(data: &[u8]) -> H256 {
+        use sha2::Digest;
+
+        H256(sha2::Sha256::digest(data).into())
+    }
+}
+
+/// Um CID (identificador de conteúdo) do IPFS, como `bafkrei...` ou `Qm...`.
+/// Ele aponta para um conteúdo pelo hash dele (um multihash), junto do codec que diz como ler
+/// os bytes. Só o multihash `sha2-256` é aceito, que é o hash que a chain sabe calcular, então
+/// o claim de um CID é o mesmo `H256` do `Sha256` dos bytes que ele identifica.
+/// É exibido como um CID da versão 1 em base32
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Cid {
+    codec: u64,
+    digest: H256,
+}
+
+impl Cid {
+    // codec de conteúdo sem estrutura, usado pelo `from_bytes`
+    const RAW: u64 = 0x55;
+
+    // codec dos blocos de arquivos do IPFS, implícito nos CIDs da versão 0
+    const DAG_PB: u64 = 0x70;
+
+    // código do sha2-256 na tabela de multihash, seguido do tamanho do hash em bytes
+    const SHA2_256: u64 = 0x12;
+    const DIGEST_LENGTH: u64 = 32;
+
+    const BASE32: &'static [u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+    const BASE58: &'static [u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+    /// Calcula o CID (versão 1, codec `raw`) de `data`, como o IPFS faz para um arquivo pequeno
+    pub fn from_bytes(data: &[u8]) -> Self {
+        Self {
+            codec: Self::RAW,
+            digest: Sha256::hash(data),
+        }
+    }
+
+    /// Lê um CID da versão 1 em base32 (`bafy...`, `bafk...`) ou da versão 0 (`Qm...`),
+    /// validando o multihash: ele precisa ser um `sha2-256` com exatamente 32 bytes
+    pub fn parse(text: &str) -> Result<Self, &'static str> {
+        // a versão 0 é só o multihash em base58, com o codec dag-pb implícito
+        if text.starts_with("Qm") {
+            return Ok(Self {
+                codec: Self::DAG_PB,
+                digest: Self::parse_multihash(&Self::decode_base58(text)?)?,
+            });
+        }
+
+        // a versão 1 começa com o prefixo multibase `b`, de base32
+        let encoded = text.strip_prefix('b').ok_or("Unsupported CID encoding")?;
+        let bytes = Self::decode_base32(encoded)?;
+        let mut rest = &bytes[..];
+        if Self::read_varint(&mut rest)? != 1 {
+            return Err("Unsupported CID version");
+        }
+        let codec = Self::read_varint(&mut rest)?;
+
+        Ok(Self {
+            codec,
+            digest: Self::parse_multihash(rest)?,
+        })
+    }
+
+    // lê o multihash: o código do hash, o tamanho e o hash em si
+    fn parse_multihash(mut bytes: &[u8]) -> Result<H256, &'static str> {
+        if Self::read_varint(&mut bytes)? != Self::SHA2_256 {
+            return Err("Unsupported multihash");
+        }
+        if Self::read_varint(&mut bytes)? != Self::DIGEST_LENGTH {
+            return Err("Invalid multihash length");
+        }
+        let digest = bytes.try_into().map_err(|_| "Invalid multihash length")?;
+        Ok(H256(digest))
+    }
+
+    // lê um inteiro sem sinal em varint (7 bits por byte, o bit mais alto indica que há mais),
+    // avançando `bytes`
+    fn read_varint(bytes: &mut &[u8]) -> Result<u64, &'static str> {
+        let mut value = 0;
+        for shift in (0..64).step_by(7) {
+            let (&byte, rest) = bytes.split_first().ok_or("Invalid CID")?;
+            *bytes = rest;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err("Invalid CID")
+    }
+
+    fn write_varint(mut value: u64, bytes: &mut Vec<u8>) {
+        while value >= 0x80 {
+            bytes.push((value & 0x7f) as u8 | 0x80);
+            value >>= 7;
+        }
+        bytes.push(value as u8);
+    }
+
+    // base32 em minúsculas e sem `=` no final, como nos CIDs
+    fn decode_base32(text: &str) -> Result<Vec<u8>, &'static str> {
+        let mut bytes = Vec::new();
+        let (mut buffer, mut bits) = (0u32, 0);
+        for char in text.bytes() {
+            let value = Self::BASE32
+                .iter()
+                .position(|&digit| digit == char)
+                .ok_or("Invalid CID")?;
+            buffer = (buffer << 5) | value as u32;
+            bits += 5;
+            if bits >= 8 {
+                bits -= 8;
+                bytes.push((buffer >> bits) as u8);
+                buffer &= (1 << bits) - 1;
+            }
+        }
+        Ok(bytes)
+    }
+
+    fn encode_base32(bytes: &[u8]) -> String {
+        let mut text = String::new();
+        let (mut buffer, mut bits) = (0u32, 0);
+        for &byte in bytes {
+            buffer = (buffer << 8) | byte as u32;
+            bits += 8;
+            while bits >= 5 {
+                bits -= 5;
+                text.push(Self::BASE32[(buffer >> bits) as usize & 31] as char);
+            }
+            buffer &= (1 << bits) - 1;
+        }
+        if bits > 0 {
+            text.push(Self::BASE32[(buffer << (5 - bits)) as usize & 31] as char);
+        }
+        text
+    }
+
+    // base58 do bitcoin: o texto é um número na base 58, e cada `1` no início é um byte zero
+    fn decode_base58(text: &str) -> Result<Vec<u8>, &'static str> {
+        // os bytes do número, do menos para o mais significativo
+        let mut bytes: Vec<u8> = Vec::new();
+        for char in text.bytes() {
+            let mut carry = Self::BASE58
+                .iter()
+                .position(|&digit| digit == char)
+                .ok_or("Invalid CID")? as u32;
+            for byte in bytes.iter_mut() {
+                carry += *byte as u32 * 58;
+                *byte = carry as u8;
+                carry >>= 8;
+            }
+            while carry > 0 {
+                bytes.push(carry as u8);
+                carry >>= 8;
+            }
+        }
+        let zeros = text.bytes().take_while(|&char| char == b'1').count();
+        bytes.extend(std::iter::repeat_n(0, zeros));
+        bytes.reverse();
+        Ok(bytes)
+    }
+}
+
+// a versão 1 do CID: a versão, o codec e o multihash, em base32 com o prefixo `b`
+impl std::fmt::Display for Cid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut bytes = Vec::new();
+        Self::write_varint(1, &mut bytes);
+        Self::write_varint(self.codec, &mut bytes);
+        Self::write_varint(Self::SHA2_256, &mut bytes);
+        Self::write_varint(Self::DIGEST_LENGTH, &mut bytes);
+        bytes.extend_from_slice(&self.digest.0);
+        write!(f, "b{}", Self::encode_base32(&bytes))
+    }
+}
+
+impl std::fmt::Debug for Cid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+// o claim de um CID é o hash do conteúdo, o mesmo que o `Sha256` calcula a partir dos bytes
+impl From<Cid> for H256 {
+    fn from(cid: Cid) -> H256 {
+        cid.digest
+    }
+}
+
+/// Como os valores da moeda da chain são exibidos: com quantas casas decimais e qual símbolo.
+/// Os saldos são guardados como inteiros na menor unidade, então com 2 casas decimais
+/// o valor `150` é exibido como `1.5 UNIT`.
+/// Um formato com mais casas decimais do que cabem em um `u128` (38) é inválido
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TokenFormat {
+    pub decimals: u32,
+    pub symbol: &'static str,
+}
+
+impl TokenFormat {
+    /// Formata `amount` com as casas decimais e o símbolo, sem zeros à direita
+    pub fn format(&self, amount: u128) -> Result<String, &'static str> {
+        let unit = self.unit()?;
+        let (whole, fraction) = (amount / unit, amount % unit);
+        if fraction == 0 {
+            return Ok(format!("{} {}", whole, self.symbol));
+        }
+
+        let fraction = format!("{:0width$}", fraction, width = self.decimals as usize);
+        Ok(format!(
+            "{}.{} {}",
+            whole,
+            fraction.trim_end_matches('0'),
+            self.symbol
+        ))
+    }
+
+    /// Lê um valor como `1.5 UNIT` (o símbolo é opcional) e o converte para a menor unidade
+    pub fn parse(&self, text: &str) -> Result<u128, &'static str> {
+        self.unit()?;
+        let mut parts = text.split_whitespace();
+        let number = parts.next().ok_or("Invalid amount")?;
+        match parts.next() {
+            None => {}
+            Some(symbol) if symbol == self.symbol => {}
+            Some(_) => return Err("Unknown token symbol"),
+        }
+        if parts.next().is_some() {
+            return Err("Invalid amount");
+        }
+
+        let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+        if fraction.len() > self.decimals as usize {
+            return Err("Too many decimal places");
+        }
+        let digits = format!(
+            "{}{:0<width$}",
+            whole,
+            fraction,
+            width = self.decimals as usize
+        );
+        if whole.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+            return Err("Invalid amount");
+        }
+
+        digits.parse().map_err(|_| "Amount overflow")
+    }
+
+    // o valor de uma unidade inteira na menor unidade, ou um erro se ele não cabe no `u128`
+    fn unit(&self) -> Result<u128, &'static str> {
+        10u128
+            .checked_pow(self.decimals)
+            .ok_or("Too many decimals for token format")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        BloomFilter, Cid, ConstU32, Get, Hasher, JournaledMap, PalletId, Sha256, TokenFormat,
+        Transactional, H256,
+    };
+
+    #[test]
+    fn pallet_accounts() {
+        let treasury = PalletId(*b"py/trsry");
+        let crowdfund = PalletId(*b"py/cfund");
+
+        // a conta é estável
+        let account: String = treasury.into_account();
+        assert_eq!(account, "modl70792f7472737279");
+        assert_eq!(treasury.into_account::<String>(), account);
+
+        // identificadores diferentes, mesmo que parecidos, têm contas diferentes
+        assert_ne!(crowdfund.into_account::<String>(), account);
+        assert_ne!(
+            PalletId([0; 8]).into_account::<String>(),
+            PalletId([0, 0, 0, 0, 0, 0, 0, 1]).into_account::<String>()
+        );
+    }
+
+    #[test]
+    fn journaled_map_rollback() {
+        let mut map: JournaledMap<&str, u32> = JournaledMap::new();
+        map.insert("miriam", 10);
+        map.insert("lucio", 20);
+
+        // tudo o que muda depois do `begin` é desfeito pelo `rollback`
+        map.begin();
+        map.insert("miriam", 11);
+        *map.get_mut(&"miriam").unwrap() += 1;
+        map.remove(&"lucio");
+        *map.get_or_default("pedro") += 5;
+        map.retain(|_, value| *value > 10);
+        map.rollback();
+        assert_eq!(
+            map.iter().collect::<Vec<_>>(),
+            vec![(&"lucio", &20), (&"miriam", &10)]
+        );
+
+        // depois do `commit`, as mudanças ficam
+        map.begin();
+        map.remove(&"miriam");
+        map.commit();
+        map.rollback();
+        assert_eq!(map.get(&"miriam"), None);
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn const_getters() {
+        assert_eq!(<ConstU32<5> as Get<u32>>::get(), 5);
+        assert_eq!(<ConstU32<5> as Get<u128>>::get(), 5);
+    }
+
+    #[test]
+    fn token_format() {
+        let unit = TokenFormat {
+            decimals: 2,
+            symbol: "UNIT",
+        };
+
+        assert_eq!(unit.format(150), Ok("1.5 UNIT".to_string()));
+        assert_eq!(unit.format(10000), Ok("100 UNIT".to_string()));
+        assert_eq!(unit.format(7), Ok("0.07 UNIT".to_string()));
+        assert_eq!(unit.format(0), Ok("0 UNIT".to_string()));
+
+        // o que é formatado pode ser lido de volta
+        assert_eq!(unit.parse("1.5 UNIT"), Ok(150));
+        assert_eq!(unit.parse(&unit.format(7).unwrap()), Ok(7));
+        assert_eq!(unit.parse("100"), Ok(10000));
+
+        assert_eq!(unit.parse("1.555 UNIT"), Err("Too many decimal places"));
+        assert_eq!(unit.parse("1.5 DOT"), Err("Unknown token symbol"));
+        assert_eq!(unit.parse("um UNIT"), Err("Invalid amount"));
+        assert_eq!(unit.parse(".5"), Err("Invalid amount"));
+        assert_eq!(unit.parse(""), Err("Invalid amount"));
+        assert_eq!(
+            unit.parse("999999999999999999999999999999999999999"),
+            Err("Amount overflow")
+        );
+
+        // com mais de 38 casas decimais, nem uma unidade inteira cabe em um `u128`
+        let invalid = TokenFormat {
+            decimals: 39,
+            symbol: "UNIT",
+        };
+        assert_eq!(invalid.format(1), Err("Too many decimals for token format"));
+        assert_eq!(
+            invalid.parse("1"),
+            Err("Too many decimals for token format")
+        );
+    }
+
+    #[test]
+    fn cids() {
+        // o CID de um arquivo vazio, como o `ipfs add --cid-version 1 --raw-leaves` calcula
+        let empty = Cid::from_bytes(b"");
+        assert_eq!(
+            empty.to_string(),
+            "bafkreihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku"
+        );
+        assert_eq!(Cid::parse(&empty.to_string()), Ok(empty));
+
+        // o claim de um CID é o hash dos mesmos bytes
+        assert_eq!(H256::from(Cid::from_bytes(b"abc")), Sha256::hash(b"abc"));
+
+        // um CID da versão 0 é lido e exibido como a versão 1 equivalente
+        let v0 = Cid::parse("QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG").unwrap();
+        assert_eq!(
+            v0.to_string(),
+            "bafybeie5nqv6kd3qnfjupgvz34woh3oksc3iau6abmyajn7qvtf6d2ho34"
+        );
+
+        // o multihash precisa ser um sha2-256 completo
+        let sha1 = format!(
+            "b{}",
+            Cid::encode_base32(&[1, 0x55, 0x11, 0x14, 0, 0, 0, 0])
+        );
+        assert_eq!(Cid::parse(&sha1), Err("Unsupported multihash"));
+        let mut short = vec![1, 0x55, 0x12, 0x20];
+        short.extend_from_slice(&[0; 31]);
+        let short = format!("b{}", Cid::encode_base32(&short));
+        assert_eq!(Cid::parse(&short), Err("Invalid multihash length"));
+
+        // e o texto precisa ser um CID em uma codificação conhecida
+        assert_eq!(
+            Cid::parse("zb2rhe5P4gXftAwvA4eXQ5HJwsER2owDyS9sKaQRRVQPn93bA"),
+            Err("Unsupported CID encoding")
+        );
+        assert_eq!(Cid::parse("bafk!"), Err("Invalid CID"));
+        assert_eq!(
+            Cid::parse(&format!("b{}", Cid::encode_base32(&[2, 0x55]))),
+            Err("Unsupported CID version")
+        );
+    }
+
+    #[test]
+    fn sha256() {
+        assert_eq!(
+            format!("{:?}", Sha256::hash(b"")),
+            "0xe3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            format!("{:?}", Sha256::hash(b"abc")),
+            "0xba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+
+        // mensagens que ocupam mais de um bloco de 64 bytes
+        let long = "a".repeat(1000);
+        assert_eq!(
+            format!("{:?}", Sha256::hash(long.as_bytes())),
+            "0x41edece42d63e8d9bf515a9ba6932e1c20cbc9f5a5d134645adb5db1b9737ea3"
+        );
+    }
+
+    #[test]
+    fn bloom_filter() {
+        let mut bloom = BloomFilter::default();
+        assert!(!bloom.contains(b"lucio"));
+
+        bloom.insert(b"lucio");
+        bloom.insert(b"miriam");
+        assert!(bloom.contains(b"lucio"));
+        assert!(bloom.contains(b"miriam"));
+
+        // com poucos itens, quase todos os outros ficam de fora
+        let false_positives = (0..1000)
+            .filter(|i| bloom.contains(format!("conta_{}", i).as_bytes()))
+            .count();
+        assert!(false_positives < 10);
+
+        // os bits vêm do SHA-256, que começa com ba 78 16 para "abc": o filtro não muda entre
+        // máquinas ou versões do compilador
+        let mut abc = BloomFilter::default();
+        abc.insert(b"abc");
+        assert_eq!(abc.bits, [1 << 22, 1 << (0x78 - 64), 1 << (0xba - 128), 0]);
+    }
+}
@@ -1,5 +1,9 @@
-use num::traits::{CheckedAdd, CheckedSub, One, Zero};
-use std::{collections::BTreeMap, ops::AddAssign};
+use crate::support::{BloomFilter, DispatchError, EventTopics};
+use num::traits::{CheckedAdd, CheckedSub, One, SaturatingAdd, Zero};
+use std::{
+    collections::BTreeMap,
+    ops::{AddAssign, RangeInclusive},
+};
 
 /**
  * Criamos uma trait para encapsular todos os types que são necessários no Pallet.
@@ -8,9 +12,54 @@ use std::{collections::BTreeMap, ops::AddAssign};
  */
 pub trait Config {
     // definição de tipos
-    type AccountId: Ord + Clone;
-    type BlockNumber: Zero + CheckedSub + CheckedAdd + Copy + One + AddAssign;
+    type AccountId: Ord + Clone + AsRef<[u8]>;
+    type BlockNumber: Zero + CheckedSub + CheckedAdd + Copy + One + AddAssign + Ord;
     type Nonce: Ord + Copy + Zero + One;
+
+    /// Tipo dos valores pagos como gorjeta (tip) nas extrinsics
+    type Amount: Zero + SaturatingAdd + Copy;
+
+    /// Quantidade máxima de extrinsics em um bloco.
+    /// Blocos com mais extrinsics que isso são rejeitados por inteiro
+    const MAX_EXTRINSICS_PER_BLOCK: u32;
+
+    /// O evento agregado do runtime, com os eventos de todos os pallets (inclusive o system).
+    /// É gerado pelo `#[macros::runtime]` como `RuntimeEvent`
+    type RuntimeEvent: EventTopics<Self::AccountId>;
+}
+
+/// Eventos emitidos pelo system ao executar cada extrinsic do bloco
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    /// A extrinsic foi executada com sucesso
+    ExtrinsicSuccess,
+
+    /// A extrinsic falhou com o erro retornado pelo pallet, junto do pallet e da chamada
+    ExtrinsicFailed { error: DispatchError },
+}
+
+// os eventos do system não citam contas
+impl<AccountId> EventTopics<AccountId> for Event {}
+
+/// Momento do bloco em que um evento foi emitido
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Phase {
+    /// Nos ganchos `on_initialize`, antes das extrinsics
+    Initialization,
+
+    /// Durante a extrinsic com esse índice dentro do bloco
+    ApplyExtrinsic(u32),
+
+    /// Nos ganchos `on_finalize`, depois das extrinsics
+    Finalization,
+}
+
+/// Um evento acompanhado do momento do bloco em que foi emitido.
+/// Assim conseguimos relacionar cada evento com a extrinsic de origem
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventRecord<E> {
+    pub phase: Phase,
+    pub event: E,
 }
 
 /**
@@ -25,6 +74,19 @@ pub struct Pallet<T: Config> {
     /// contador de transações que cada usuário (user_wallet_address) já fez na blockchain
     /// <user_wallet_address, counter_of_transactions>
     nonce: BTreeMap<T::AccountId, T::Nonce>,
+
+    /// eventos de todos os pallets emitidos em cada bloco, na ordem em que foram depositados
+    /// <block_number, eventos_do_bloco>
+    events: BTreeMap<T::BlockNumber, Vec<EventRecord<T::RuntimeEvent>>>,
+
+    /// filtro de Bloom com as contas citadas nos eventos de cada bloco.
+    /// Permite pular os blocos que com certeza não têm eventos de uma conta
+    /// <block_number, contas_dos_eventos>
+    event_blooms: BTreeMap<T::BlockNumber, BloomFilter>,
+
+    /// soma das gorjetas pagas pelas extrinsics de cada bloco
+    /// <block_number, total_de_gorjetas>
+    tips: BTreeMap<T::BlockNumber, T::Amount>,
 }
 
 impl<T: Config> Pallet<T> {
@@ -34,6 +96,9 @@ impl<T: Config> Pallet<T> {
         Pallet {
             block_number: T::BlockNumber::zero(),
             nonce: BTreeMap::new(),
+            events: BTreeMap::new(),
+            event_blooms: BTreeMap::new(),
+            tips: BTreeMap::new(),
         }
     }
 
@@ -53,15 +118,94 @@ impl<T: Config> Pallet<T> {
             .unwrap();
     }
 
+    /// Deposita um evento no bloco atual, emitido na fase `phase` do bloco
+    pub fn deposit_event(&mut self, phase: Phase, event: T::RuntimeEvent) {
+        let bloom = self.event_blooms.entry(self.block_number).or_default();
+        for account in event.accounts() {
+            bloom.insert(account.as_ref());
+        }
+
+        self.events
+            .entry(self.block_number)
+            .or_default()
+            .push(EventRecord { phase, event });
+    }
+
+    /// Eventos emitidos no bloco atual
+    pub fn events(&self) -> &[EventRecord<T::RuntimeEvent>] {
+        self.events_at(&self.block_number)
+    }
+
+    /// Eventos emitidos em um bloco específico. Retorna vazio se o bloco não emitiu eventos
+    pub fn events_at(&self, block_number: &T::BlockNumber) -> &[EventRecord<T::RuntimeEvent>] {
+        self.events
+            .get(block_number)
+            .map(|events| events.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Eventos que citam `account` nos blocos de `range`, junto do número do bloco.
+    /// Os blocos cujo filtro de Bloom não tem a conta nem são percorridos
+    pub fn events_for_account(
+        &self,
+        account: &T::AccountId,
+        range: RangeInclusive<T::BlockNumber>,
+    ) -> Vec<(T::BlockNumber, &EventRecord<T::RuntimeEvent>)> {
+        self.events
+            .range(range)
+            .filter(|(block_number, _)| {
+                self.event_blooms
+                    .get(block_number)
+                    .is_some_and(|bloom| bloom.contains(account.as_ref()))
+            })
+            .flat_map(|(block_number, records)| {
+                records
+                    .iter()
+                    .filter(|record| record.event.accounts().contains(&account))
+                    .map(|record| (*block_number, record))
+            })
+            .collect()
+    }
+
+    /// Soma `tip` ao total de gorjetas do bloco atual.
+    /// O total é só um registro, então ele satura no maior valor de `Amount` em vez de falhar
+    pub fn note_tip(&mut self, tip: T::Amount) {
+        if tip.is_zero() {
+            return;
+        }
+        let total = self
+            .tips
+            .entry(self.block_number)
+            .or_insert(T::Amount::zero());
+        *total = total.saturating_add(&tip);
+    }
+
+    /// Total de gorjetas pagas no bloco atual
+    pub fn tips(&self) -> T::Amount {
+        self.tips_at(&self.block_number)
+    }
+
+    /// Total de gorjetas pagas em um bloco específico
+    pub fn tips_at(&self, block_number: &T::BlockNumber) -> T::Amount {
+        *self.tips.get(block_number).unwrap_or(&T::Amount::zero())
+    }
+
     pub fn inc_nonce(&mut self, account: &T::AccountId) {
-        // se o nonce não existir, o valor é 1,
-        let nonce = *self.nonce.get(account).unwrap_or(&T::Nonce::zero()) + T::Nonce::one();
-        self.nonce.insert(account.clone(), nonce);
+        // se o nonce não existir, o valor é 1.
+        // só clonamos o `AccountId` na primeira transação da conta
+        match self.nonce.get_mut(account) {
+            Some(nonce) => *nonce = *nonce + T::Nonce::one(),
+            None => {
+                self.nonce.insert(account.clone(), T::Nonce::one());
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod test {
+    use super::Phase;
+    use crate::support::DispatchError;
 
     struct TestConfig;
 
@@ -70,6 +214,9 @@ mod test {
         type AccountId = String;
         type BlockNumber = u32;
         type Nonce = u32;
+        type Amount = u32;
+        const MAX_EXTRINSICS_PER_BLOCK: u32 = 10;
+        type RuntimeEvent = super::Event;
     }
 
     #[test]
@@ -92,4 +239,84 @@ mod test {
         // o nonce de Alice agora é 1?
         assert_eq!(system.get_nonce(&"Alice".to_string()), 1);
     }
+
+    #[test]
+    fn inc_nonce_existing_account() {
+        let mut system: super::Pallet<TestConfig> = super::Pallet::new();
+
+        // o primeiro incremento cria a entrada, os seguintes apenas atualizam
+        system.inc_nonce(&"Alice".to_string());
+        system.inc_nonce(&"Alice".to_string());
+        system.inc_nonce(&"Bob".to_string());
+
+        assert_eq!(system.get_nonce(&"Alice".to_string()), 2);
+        assert_eq!(system.get_nonce(&"Bob".to_string()), 1);
+    }
+
+    #[test]
+    fn events_per_block() {
+        let mut system: super::Pallet<TestConfig> = super::Pallet::new();
+
+        // bloco 1 com duas extrinsics
+        system.inc_block_number();
+        system.deposit_event(Phase::ApplyExtrinsic(0), super::Event::ExtrinsicSuccess);
+        system.deposit_event(
+            Phase::ApplyExtrinsic(1),
+            super::Event::ExtrinsicFailed {
+                error: "Insufficient balance".into(),
+            },
+        );
+
+        // bloco 2 sem eventos
+        system.inc_block_number();
+        assert!(system.events().is_empty());
+
+        // os eventos do bloco 1 continuam consultáveis
+        let events = system.events_at(&1);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].phase, Phase::ApplyExtrinsic(0));
+        assert_eq!(events[0].event, super::Event::ExtrinsicSuccess);
+        assert_eq!(events[1].phase, Phase::ApplyExtrinsic(1));
+        assert_eq!(
+            events[1].event,
+            super::Event::ExtrinsicFailed {
+                error: "Insufficient balance".into()
+            }
+        );
+    }
+
+    #[test]
+    fn dispatch_error_context() {
+        let error = DispatchError {
+            pallet: Some("balances"),
+            call: Some("transfer"),
+            error: "Insufficient balance",
+        };
+        assert_eq!(error.to_string(), "balances.transfer: Insufficient balance");
+
+        // sem contexto, apenas a mensagem é exibida
+        let error: DispatchError = "Cannot pay transaction fee".into();
+        assert_eq!(error.to_string(), "Cannot pay transaction fee");
+    }
+
+    #[test]
+    fn tips_per_block() {
+        let mut system: super::Pallet<TestConfig> = super::Pallet::new();
+
+        system.inc_block_number();
+        system.note_tip(5);
+        system.note_tip(0);
+        system.note_tip(7);
+        assert_eq!(system.tips(), 12);
+
+        // um novo bloco começa sem gorjetas
+        system.inc_block_number();
+        assert_eq!(system.tips(), 0);
+        assert_eq!(system.tips_at(&1), 12);
+
+        // o total satura em vez de entrar em pânico
+        system.note_tip(u32::MAX);
+        system.note_tip(1);
+        assert_eq!(system.tips(), u32::MAX);
+    }
 }
@@ -0,0 +1,379 @@
+use crate::support::{
+    ensure_root, Currency, DispatchResult, EventTopics, Hooks, Origin, Transactional,
+};
+use num::traits::{CheckedAdd, Zero};
+
+pub trait Config {
+    type AccountId: Ord + Clone;
+    type Amount: Zero + CheckedAdd + Copy;
+
+    /// A moeda em que as taxas são cobradas. No runtime, é o pallet `balances`
+    type Currency: Currency<Self::AccountId, Self::Amount>;
+}
+
+/// Eventos emitidos pelo módulo de taxas
+#[macros::event]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event<T: Config> {
+    /// `who` pagou a taxa `fee` e a gorjeta `tip` para ter sua extrinsic executada
+    TransactionFeePaid {
+        who: T::AccountId,
+        fee: T::Amount,
+        tip: T::Amount,
+    },
+
+    /// O destino das taxas foi alterado. Com `None`, as taxas passam a ser queimadas
+    FeeDestinationSet { destination: Option<T::AccountId> },
+}
+
+/// Erros retornados pelo módulo de taxas
+#[macros::error]
+pub mod errors {
+    /// Quem assinou a extrinsic não tem saldo livre para pagar a taxa mais a gorjeta
+    pub const CANNOT_PAY_TRANSACTION_FEE: &str = "Cannot pay transaction fee";
+
+    /// A soma da taxa com a gorjeta não cabe no tipo `Amount`
+    pub const TIP_OVERFLOW: &str = "Overflow when adding tip to fee";
+
+    /// O total de taxas cobradas não cabe mais no tipo `Amount`
+    pub const TOTAL_FEES_OVERFLOW: &str = "Overflow when adding to total fees";
+}
+
+/// Módulo responsável por cobrar as taxas das extrinsics.
+/// O runtime chama `charge_fee` antes de executar cada extrinsic,
+/// com a taxa base declarada pela chamada
+#[derive(Debug)]
+pub struct Pallet<T: Config> {
+    // conta que recebe as taxas cobradas. Se for `None`, as taxas são queimadas
+    fee_destination: Option<T::AccountId>,
+
+    // soma de todas as taxas (e gorjetas) cobradas até agora
+    total_fees: T::Amount,
+
+    // eventos emitidos durante a extrinsic atual, ainda não recolhidos pelo runtime
+    events: Vec<Event<T>>,
+
+    // o destino e o total das taxas no `begin` da transação aberta, para o `rollback`
+    checkpoint: Option<(Option<T::AccountId>, T::Amount)>,
+}
+
+#[macros::call]
+impl<T: Config> Pallet<T> {
+    /// Define a conta que recebe as taxas, ou `None` para queimá-las.
+    /// Só pode ser chamada com a origem `Root`
+    pub fn set_fee_destination(
+        &mut self,
+        origin: Origin<T::AccountId>,
+        destination: Option<T::AccountId>,
+    ) -> DispatchResult {
+        ensure_root(origin)?;
+
+        self.fee_destination = destination.clone();
+        self.deposit_event(Event::FeeDestinationSet { destination });
+
+        Ok(())
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    pub fn new() -> Self {
+        Self {
+            fee_destination: None,
+            total_fees: T::Amount::zero(),
+            events: Vec::new(),
+            checkpoint: None,
+        }
+    }
+
+    /// Cobra `fee` mais a gorjeta `tip` de `who`, enviando o valor para o destino das taxas
+    /// ou queimando-o. Falha, sem alterar nada, se `who` não puder pagar o total.
+    ///
+    /// O valor é retirado de `who` e depositado no destino, e não transferido:
+    /// as taxas chegam ao destino mesmo que ele esteja congelado ou ainda não exista
+    pub fn charge_fee(
+        &mut self,
+        currency: &mut T::Currency,
+        who: &T::AccountId,
+        fee: T::Amount,
+        tip: T::Amount,
+    ) -> DispatchResult {
+        let total = fee.checked_add(&tip).ok_or(errors::TIP_OVERFLOW)?;
+        if total.is_zero() {
+            return Ok(());
+        }
+
+        let new_total_fees = self
+            .total_fees
+            .checked_add(&total)
+            .ok_or(errors::TOTAL_FEES_OVERFLOW)?;
+
+        currency
+            .withdraw(who, total)
+            .map_err(|_| errors::CANNOT_PAY_TRANSACTION_FEE)?;
+        if let Some(destination) = &self.fee_destination {
+            currency.deposit(destination, total)?;
+        }
+
+        self.total_fees = new_total_fees;
+        self.deposit_event(Event::TransactionFeePaid {
+            who: who.clone(),
+            fee,
+            tip,
+        });
+
+        Ok(())
+    }
+
+    /// Conta que recebe as taxas, ou `None` se elas são queimadas
+    pub fn fee_destination(&self) -> Option<&T::AccountId> {
+        self.fee_destination.as_ref()
+    }
+
+    /// Soma de todas as taxas cobradas, incluindo as gorjetas
+    pub fn total_fees(&self) -> T::Amount {
+        self.total_fees
+    }
+
+    /// Retira os eventos emitidos desde a última chamada.
+    /// O runtime chama essa função ao final de cada extrinsic
+    pub fn take_events(&mut self) -> Vec<Event<T>> {
+        std::mem::take(&mut self.events)
+    }
+
+    fn deposit_event(&mut self, event: Event<T>) {
+        self.events.push(event);
+    }
+}
+
+/// Estado inicial (gênesis) do módulo de taxas
+pub struct GenesisConfig<T: Config> {
+    /// Conta que recebe as taxas desde o primeiro bloco, ou `None` para queimá-las
+    pub fee_destination: Option<T::AccountId>,
+}
+
+impl<T: Config> GenesisConfig<T> {
+    /// Constrói o pallet a partir da configuração de gênesis
+    pub fn build(&self) -> Pallet<T> {
+        let mut pallet = Pallet::new();
+        pallet.fee_destination = self.fee_destination.clone();
+        pallet
+    }
+}
+
+impl<T: Config> EventTopics<T::AccountId> for Event<T> {
+    fn accounts(&self) -> Vec<&T::AccountId> {
+        match self {
+            Event::TransactionFeePaid { who, .. } => vec![who],
+            Event::FeeDestinationSet { destination } => destination.iter().collect(),
+        }
+    }
+}
+
+// a cobrança de taxas acontece nas extrinsics, não no início ou no fim dos blocos
+impl<T: Config, BlockNumber> Hooks<BlockNumber> for Pallet<T> {}
+
+impl<T: Config> Transactional for Pallet<T> {
+    fn begin(&mut self) {
+        self.checkpoint = Some((self.fee_destination.clone(), self.total_fees));
+    }
+
+    fn commit(&mut self) {
+        self.checkpoint = None;
+    }
+
+    fn rollback(&mut self) {
+        if let Some((fee_destination, total_fees)) = self.checkpoint.take() {
+            self.fee_destination = fee_destination;
+            self.total_fees = total_fees;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Event;
+    use crate::balances;
+    use crate::support::{ConstU32, Currency, DispatchResult, Origin};
+    use std::collections::BTreeMap;
+
+    #[derive(Debug, PartialEq)]
+    struct TestConfig;
+
+    impl balances::Config for TestConfig {
+        type AccountId = String;
+        type Amount = u32;
+        const EXISTENTIAL_DEPOSIT: u32 = 10;
+        type MinimumTransfer = ConstU32<5>;
+        const MAX_MEMO_LENGTH: u32 = 8;
+        const ACCRUAL: Option<balances::Accrual> = None;
+        type OnDeposit = ();
+        type OnWithdraw = ();
+        type OnDust = ();
+    }
+
+    impl super::Config for TestConfig {
+        type AccountId = String;
+        type Amount = u32;
+        type Currency = balances::Pallet<TestConfig>;
+    }
+
+    #[test]
+    fn fees_are_burned_or_sent_to_destination() {
+        let mut balances = balances::Pallet::<TestConfig>::new();
+        let mut payment = super::Pallet::<TestConfig>::new();
+        let miriam = "miriam".to_string();
+        let treasury = "treasury".to_string();
+
+        balances.set_balance(&miriam, 100).unwrap();
+
+        // sem destino, a taxa é queimada
+        assert_eq!(payment.charge_fee(&mut balances, &miriam, 5, 0), Ok(()));
+        assert_eq!(balances.get_balance(&miriam), 95);
+        assert_eq!(balances.total_issuance(), 95);
+
+        // só Root pode definir o destino das taxas
+        assert_eq!(
+            payment.set_fee_destination(Origin::Signed(miriam.clone()), Some(treasury.clone())),
+            Err("BadOrigin")
+        );
+        assert_eq!(
+            payment.set_fee_destination(Origin::Root, Some(treasury.clone())),
+            Ok(())
+        );
+
+        // com destino, a taxa e a gorjeta são transferidas e o total em circulação não muda
+        assert_eq!(payment.charge_fee(&mut balances, &miriam, 15, 5), Ok(()));
+        assert_eq!(balances.get_balance(&miriam), 75);
+        assert_eq!(balances.get_balance(&treasury), 20);
+        assert_eq!(balances.total_issuance(), 95);
+        assert_eq!(payment.total_fees(), 25);
+
+        // quem não pode pagar a taxa mais a gorjeta não é cobrado
+        assert_eq!(
+            payment.charge_fee(&mut balances, &miriam, 5, 1000),
+            Err("Cannot pay transaction fee")
+        );
+        assert_eq!(balances.get_balance(&miriam), 75);
+        assert_eq!(payment.total_fees(), 25);
+
+        assert_eq!(
+            payment.take_events(),
+            vec![
+                Event::TransactionFeePaid {
+                    who: miriam.clone(),
+                    fee: 5,
+                    tip: 0
+                },
+                Event::FeeDestinationSet {
+                    destination: Some(treasury)
+                },
+                Event::TransactionFeePaid {
+                    who: miriam,
+                    fee: 15,
+                    tip: 5
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn fees_skip_transfer_checks_on_destination() {
+        let mut balances = balances::Pallet::<TestConfig>::new();
+        let mut payment = super::Pallet::<TestConfig>::new();
+        let miriam = "miriam".to_string();
+        let treasury = "treasury".to_string();
+
+        balances.set_balance(&miriam, 100).unwrap();
+        payment
+            .set_fee_destination(Origin::Root, Some(treasury.clone()))
+            .unwrap();
+
+        // uma taxa abaixo do depósito existencial ainda é cobrada, mesmo com o destino vazio.
+        // como ela não basta para criar a conta do destino, é destruída como poeira
+        assert_eq!(payment.charge_fee(&mut balances, &miriam, 5, 0), Ok(()));
+        assert_eq!(balances.get_balance(&miriam), 95);
+        assert!(!balances.account_exists(&treasury));
+        assert_eq!(balances.total_issuance(), 95);
+        assert_eq!(payment.total_fees(), 5);
+
+        // um destino congelado continua recebendo as taxas,
+        // e, depois de existir, recebe também as taxas pequenas
+        balances.freeze(Origin::Root, treasury.clone()).unwrap();
+        assert_eq!(payment.charge_fee(&mut balances, &miriam, 10, 2), Ok(()));
+        assert_eq!(payment.charge_fee(&mut balances, &miriam, 1, 0), Ok(()));
+        assert_eq!(balances.get_balance(&miriam), 82);
+        assert_eq!(balances.get_balance(&treasury), 13);
+        assert_eq!(balances.total_issuance(), 95);
+    }
+
+    // moeda de mentira: só saldos livres, sem depósito existencial nem reservas
+    #[derive(Default)]
+    struct MockCurrency {
+        balances: BTreeMap<String, u32>,
+    }
+
+    impl Currency<String, u32> for MockCurrency {
+        fn free_balance(&self, who: &String) -> u32 {
+            *self.balances.get(who).unwrap_or(&0)
+        }
+
+        fn total_issuance(&self) -> u32 {
+            self.balances.values().sum()
+        }
+
+        fn transfer(&mut self, from: &String, to: &String, amount: u32) -> DispatchResult {
+            self.withdraw(from, amount)?;
+            self.deposit(to, amount)
+        }
+
+        fn withdraw(&mut self, who: &String, amount: u32) -> DispatchResult {
+            let balance = self.balances.entry(who.clone()).or_default();
+            *balance = balance.checked_sub(amount).ok_or("Insufficient balance")?;
+            Ok(())
+        }
+
+        fn deposit(&mut self, who: &String, amount: u32) -> DispatchResult {
+            *self.balances.entry(who.clone()).or_default() += amount;
+            Ok(())
+        }
+
+        fn reserve(&mut self, _who: &String, _amount: u32) -> DispatchResult {
+            Err("Not supported")
+        }
+
+        fn unreserve(&mut self, _who: &String, amount: u32) -> u32 {
+            amount
+        }
+
+        fn slash(&mut self, _who: &String, amount: u32) -> (u32, u32) {
+            (0, amount)
+        }
+    }
+
+    struct MockConfig;
+
+    impl super::Config for MockConfig {
+        type AccountId = String;
+        type Amount = u32;
+        type Currency = MockCurrency;
+    }
+
+    #[test]
+    fn fees_with_mock_currency() {
+        let mut currency = MockCurrency::default();
+        let mut payment = super::Pallet::<MockConfig>::new();
+        let miriam = "miriam".to_string();
+        currency.balances.insert(miriam.clone(), 10);
+
+        // o pallet só depende da trait `Currency`, e não do `balances`
+        assert_eq!(payment.charge_fee(&mut currency, &miriam, 3, 1), Ok(()));
+        assert_eq!(currency.free_balance(&miriam), 6);
+        assert_eq!(currency.total_issuance(), 6);
+        assert_eq!(
+            payment.charge_fee(&mut currency, &miriam, 7, 0),
+            Err("Cannot pay transaction fee")
+        );
+        assert_eq!(currency.free_balance(&miriam), 6);
+        assert_eq!(payment.total_fees(), 4);
+    }
+}